@@ -0,0 +1,52 @@
+use serde::Deserialize;
+
+pub const SATS_PER_BTC: f64 = 100_000_000.0;
+
+/// https://github.com/Blockstream/esplora/blob/master/API.md#get-addressaddress
+#[derive(Debug, Clone, Deserialize)]
+pub struct EsploraAddress {
+    pub address: String,
+    pub chain_stats: TxoStats,
+    pub mempool_stats: TxoStats
+}
+
+impl EsploraAddress {
+    pub fn is_used(&self) -> bool {
+        self.chain_stats.tx_count > 0 || self.mempool_stats.tx_count > 0
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TxoStats {
+    pub funded_txo_sum: u64,
+    pub spent_txo_sum: u64,
+    pub tx_count: u64
+}
+
+impl TxoStats {
+    pub fn balance_sats(&self) -> i64 {
+        self.funded_txo_sum as i64 - self.spent_txo_sum as i64
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BitcoinBalance {
+    pub confirmed_sats: i64,
+    /// Could be negative when the mempool spends confirmed coins
+    pub unconfirmed_sats: i64
+}
+
+impl BitcoinBalance {
+    pub fn add(&mut self, address: &EsploraAddress) {
+        self.confirmed_sats += address.chain_stats.balance_sats();
+        self.unconfirmed_sats += address.mempool_stats.balance_sats();
+    }
+
+    pub fn confirmed_btc(&self) -> f64 {
+        self.confirmed_sats as f64 / SATS_PER_BTC
+    }
+
+    pub fn total_btc(&self) -> f64 {
+        (self.confirmed_sats + self.unconfirmed_sats) as f64 / SATS_PER_BTC
+    }
+}
@@ -0,0 +1,78 @@
+use std::error::Error;
+use futures::future::join_all;
+use reqwest::header::HeaderMap;
+
+use crate::blockchain::bitcoin::dto::{BitcoinBalance, EsploraAddress};
+use crate::blockchain::bitcoin::xpub::{derive_address, is_extended_public_key, parse_xpub};
+use crate::utils::error::HttpError;
+use crate::utils::http_client::HttpClient;
+
+/// Client of an Esplora compatible REST api, like https://blockstream.info/api or a self-hosted electrs
+pub struct EsploraClient {
+    pub base_url: String,
+    client: HttpClient
+}
+
+impl EsploraClient {
+    pub fn new(base_url: String) -> Self {
+        Self { base_url: base_url.trim_end_matches('/').to_string(), client: HttpClient::new("Esplora".to_string()) }
+    }
+
+    pub async fn fetch_address(&self, address: &str) -> Result<EsploraAddress, HttpError> {
+        let response = self.client.send_request(
+            self.base_url.clone(),
+            format!("/address/{address}"),
+            HeaderMap::new()
+        ).await?;
+
+        if !response.status().is_success() {
+            eprintln!("[Esplora] Failed to fetch address {address}: {:?}", response.status());
+            return Err(HttpError::ResponseError)
+        }
+        response.json::<EsploraAddress>().await.map_err(|_| HttpError::ResponseDataError)
+    }
+
+    /// Balance of one address, or of every used address of an xpub/ypub/zpub until `gap_limit` unused ones in a row.
+    /// `gap_limit` is at least 1, otherwise no address is derived and the balance is always 0.
+    pub async fn fetch_balance(&self, address: &str, gap_limit: u32) -> Result<BitcoinBalance, Box<dyn Error>> {
+        let gap_limit = gap_limit.max(1);
+        let mut balance = BitcoinBalance::default();
+        if !is_extended_public_key(address) {
+            balance.add(&self.fetch_address(address).await?);
+            return Ok(balance)
+        }
+
+        let xpub = parse_xpub(address)?;
+        // Receiving addresses and then change addresses
+        for change in [0, 1] {
+            let mut next_index = 0;
+            let mut unused_in_a_row = 0;
+            while unused_in_a_row < gap_limit {
+                let addresses = (next_index..next_index + gap_limit)
+                    .map(|index| derive_address(&xpub, change, index))
+                    .collect::<Result<Vec<String>, _>>()?;
+                next_index += gap_limit;
+
+                let results = join_all(addresses.iter().map(|address| self.fetch_address(address))).await;
+                for result in results {
+                    let esplora_address = result?;
+                    if esplora_address.is_used() {
+                        unused_in_a_row = 0;
+                        println!(
+                            "[Esplora] {} balance: {} sats, unconfirmed: {} sats", esplora_address.address,
+                            esplora_address.chain_stats.balance_sats(), esplora_address.mempool_stats.balance_sats()
+                        );
+                        balance.add(&esplora_address);
+                    } else {
+                        unused_in_a_row += 1;
+                        if unused_in_a_row >= gap_limit {
+                            break
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(balance)
+    }
+}
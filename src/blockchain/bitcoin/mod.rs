@@ -0,0 +1,3 @@
+pub mod dto;
+pub mod esplora;
+pub mod xpub;
\ No newline at end of file
@@ -0,0 +1,48 @@
+use bech32::{u5, ToBase32, Variant};
+use coins_bip32::ecdsa::VerifyingKey;
+use coins_bip32::enc::encode_b58_check;
+use coins_bip32::prelude::{Bip32Error, Hint, MainnetEncoder, Parent, XKeyEncoder, XKeyInfo, XPub};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+pub fn is_extended_public_key(address: &str) -> bool {
+    ["xpub", "ypub", "zpub"].iter().any(|prefix| address.starts_with(prefix))
+}
+
+pub fn parse_xpub(xpub: &str) -> Result<XPub, Bip32Error> {
+    MainnetEncoder::xpub_from_base58(xpub)
+}
+
+/// Address of `{change}/{index}` under the account xpub,
+/// P2PKH for xpub, P2SH-P2WPKH for ypub and P2WPKH for zpub
+pub fn derive_address(xpub: &XPub, change: u32, index: u32) -> Result<String, Bip32Error> {
+    let child = xpub.derive_child(change)?.derive_child(index)?;
+    let public_key: &VerifyingKey = child.as_ref();
+    let pubkey_hash = hash160(&public_key.to_sec1_bytes());
+
+    let xkey_info: &XKeyInfo = xpub.as_ref();
+    let address = match xkey_info.hint {
+        Hint::Legacy => base58_address(0x00, &pubkey_hash),
+        Hint::Compatibility => {
+            let mut redeem_script = vec!(0x00, 0x14);
+            redeem_script.extend(&pubkey_hash);
+            base58_address(0x05, &hash160(&redeem_script))
+        }
+        Hint::SegWit => {
+            let mut data = vec!(u5::try_from_u8(0).unwrap());
+            data.extend(pubkey_hash.to_base32());
+            bech32::encode("bc", data, Variant::Bech32).expect("Failed to encode a bech32 address")
+        }
+    };
+    Ok(address)
+}
+
+fn hash160(data: &[u8]) -> Vec<u8> {
+    Ripemd160::digest(Sha256::digest(data)).to_vec()
+}
+
+fn base58_address(version: u8, hash: &[u8]) -> String {
+    let mut payload = vec!(version);
+    payload.extend(hash);
+    encode_b58_check(&payload)
+}
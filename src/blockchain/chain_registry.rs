@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::utils::config_struct::ChainConfig;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainInfo {
+    pub chain_id: u32,
+    pub name: String,
+    pub native_symbol: String,
+    pub decimals: u8,
+    pub block_time_secs: f64,
+    /// `{address}` is replaced by the address
+    pub address_url_template: Option<String>,
+    /// `{tx_hash}` is replaced by the transaction hash
+    pub tx_url_template: Option<String>
+}
+
+impl ChainInfo {
+    pub fn address_url(&self, address: &str) -> Option<String> {
+        self.address_url_template.as_ref().map(|template| template.replace("{address}", address))
+    }
+
+    pub fn tx_url(&self, tx_hash: &str) -> Option<String> {
+        self.tx_url_template.as_ref().map(|template| template.replace("{tx_hash}", tx_hash))
+    }
+}
+
+/// Built-in EVM chains merged with the `[chains]` overrides in config.toml
+pub struct ChainRegistry {
+    chains: HashMap<u32, ChainInfo>
+}
+
+impl ChainRegistry {
+    pub fn from_config(overrides: &HashMap<String, ChainConfig>) -> Self {
+        let mut chains: HashMap<u32, ChainInfo> = built_in_chains().into_iter()
+            .map(|chain| (chain.chain_id, chain))
+            .collect();
+
+        for (chain_id, config) in overrides {
+            let Ok(chain_id) = chain_id.parse::<u32>() else {
+                eprintln!("[Chain] {chain_id} is not a valid chain id, skipped");
+                continue
+            };
+            match chains.get_mut(&chain_id) {
+                Some(chain) => apply_override(chain, config.clone()),
+                None => match config.native_symbol.clone() {
+                    Some(native_symbol) => {
+                        let mut chain = ChainInfo {
+                            chain_id,
+                            name: format!("Chain {chain_id}"),
+                            native_symbol,
+                            decimals: 18,
+                            block_time_secs: 12.0,
+                            address_url_template: None,
+                            tx_url_template: None
+                        };
+                        apply_override(&mut chain, config.clone());
+                        chains.insert(chain_id, chain);
+                    }
+                    None => eprintln!("[Chain] native_symbol of the unknown chain id {chain_id} is required, skipped")
+                }
+            }
+        }
+
+        Self { chains }
+    }
+
+    pub fn get(&self, chain_id: u32) -> Option<&ChainInfo> {
+        self.chains.get(&chain_id)
+    }
+}
+
+fn apply_override(chain: &mut ChainInfo, config: ChainConfig) {
+    if let Some(name) = config.name {
+        chain.name = name;
+    }
+    if let Some(native_symbol) = config.native_symbol {
+        chain.native_symbol = native_symbol;
+    }
+    if let Some(decimals) = config.decimals {
+        chain.decimals = decimals;
+    }
+    if let Some(block_time_secs) = config.block_time_secs {
+        chain.block_time_secs = block_time_secs;
+    }
+    if config.address_url_template.is_some() {
+        chain.address_url_template = config.address_url_template;
+    }
+    if config.tx_url_template.is_some() {
+        chain.tx_url_template = config.tx_url_template;
+    }
+}
+
+fn built_in_chains() -> Vec<ChainInfo> {
+    vec!(
+        explorer_chain(1, "Ethereum", "ETH", 12.0, "https://etherscan.io"),
+        explorer_chain(10, "Optimism", "ETH", 2.0, "https://optimistic.etherscan.io"),
+        explorer_chain(56, "BNB Smart Chain", "BNB", 3.0, "https://bscscan.com"),
+        explorer_chain(137, "Polygon", "POL", 2.0, "https://polygonscan.com"),
+        explorer_chain(324, "zkSync Era", "ETH", 1.0, "https://explorer.zksync.io"),
+        explorer_chain(8453, "Base", "ETH", 2.0, "https://basescan.org"),
+        explorer_chain(42161, "Arbitrum One", "ETH", 0.25, "https://arbiscan.io"),
+        explorer_chain(43114, "Avalanche C-Chain", "AVAX", 2.0, "https://snowtrace.io"),
+        explorer_chain(59144, "Linea", "ETH", 2.0, "https://lineascan.build"),
+        explorer_chain(11155111, "Sepolia", "ETH", 12.0, "https://sepolia.etherscan.io"),
+    )
+}
+
+/// Every built-in explorer uses the Etherscan url paths
+fn explorer_chain(chain_id: u32, name: &str, native_symbol: &str, block_time_secs: f64, explorer_url: &str) -> ChainInfo {
+    ChainInfo {
+        chain_id,
+        name: name.to_string(),
+        native_symbol: native_symbol.to_string(),
+        decimals: 18,
+        block_time_secs,
+        address_url_template: Some(format!("{explorer_url}/address/{{address}}")),
+        tx_url_template: Some(format!("{explorer_url}/tx/{{tx_hash}}"))
+    }
+}
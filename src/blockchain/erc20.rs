@@ -0,0 +1,10 @@
+use ethers::contract::abigen;
+
+abigen!(
+    Erc20,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function decimals() external view returns (uint8)
+        function symbol() external view returns (string)
+    ]"#
+);
@@ -1 +1,5 @@
-pub mod node_provider;
\ No newline at end of file
+pub mod node_provider;
+pub mod erc20;
+pub mod chain_registry;
+pub mod bitcoin;
+pub mod solana;
\ No newline at end of file
@@ -1,10 +1,144 @@
 use std::env;
+use std::error::Error;
+use std::future::Future;
+use std::sync::Arc;
+use ethers::core::types::{BlockId, BlockNumber};
+use ethers::providers::{Http, Middleware, Provider};
+use futures::future::join_all;
+use tokio::time::{timeout, Duration};
 
-use ethers::providers::{Http, Provider};
+use crate::utils::config_struct::NodeProviderConfig;
+use crate::utils::error::NodeError;
 
-pub fn build_ethers_provider(chain_id: u32) -> Provider<Http> {
-    let node_env_name = format!("NODE_URL_{chain_id}");
-    let node_url = env::var(node_env_name)
-        .expect(&*format!("Node provider url of chain id {chain_id} not found"));
-    return Provider::try_from(node_url).expect("Failed to build a ethers provider");
+pub type NodeResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+pub struct NodeEndpoint {
+    /// Name of the env var holding the url, so the url itself is never logged
+    pub name: String,
+    pub provider: Arc<Provider<Http>>
+}
+
+/// All the RPC endpoints of one chain, they are tried in order until one of them answers
+pub struct NodeProvider {
+    pub chain_id: u32,
+    pub endpoints: Vec<NodeEndpoint>,
+    pub timeout: Duration,
+    /// Only return a value when the majority of endpoints agree on it at the same block
+    pub quorum: bool
+}
+
+pub fn build_ethers_provider(chain_id: u32, config: Option<&NodeProviderConfig>) -> Result<NodeProvider, NodeError> {
+    let env_names = match config {
+        Some(config) if !config.urls_env.is_empty() => config.urls_env.clone(),
+        _ => default_env_names(chain_id)
+    };
+
+    let endpoints: Vec<NodeEndpoint> = env_names.into_iter()
+        .filter_map(|name| {
+            let node_url = env::var(&name).ok()?;
+            match Provider::<Http>::try_from(node_url) {
+                Ok(provider) => Some(NodeEndpoint { name, provider: Arc::new(provider) }),
+                Err(e) => {
+                    eprintln!("[Node] Invalid node url in {name}: {e}");
+                    None
+                }
+            }
+        })
+        .collect();
+    if endpoints.is_empty() {
+        return Err(NodeError::NoEndpoint(chain_id))
+    }
+
+    Ok(NodeProvider {
+        chain_id,
+        endpoints,
+        timeout: Duration::from_secs(config.map_or(NodeProviderConfig::default().timeout_secs, |config| config.timeout_secs)),
+        quorum: config.is_some_and(|config| config.quorum)
+    })
+}
+
+/// `NODE_URL_{chain_id}`, then `NODE_URL_{chain_id}_1`, `NODE_URL_{chain_id}_2`... until one of them is missing
+fn default_env_names(chain_id: u32) -> Vec<String> {
+    let mut env_names = vec![format!("NODE_URL_{chain_id}")];
+    env_names.extend(
+        (1..).map(|n| format!("NODE_URL_{chain_id}_{n}")).take_while(|name| env::var(name).is_ok())
+    );
+    env_names
+}
+
+impl NodeProvider {
+    /// Read a value at the latest block, or at the same block on every endpoint in quorum mode
+    pub async fn read<T, F, Fut>(&self, call: F) -> Result<T, NodeError>
+    where
+        T: PartialEq,
+        F: Fn(Arc<Provider<Http>>, BlockId) -> Fut,
+        Fut: Future<Output = NodeResult<T>>
+    {
+        if self.quorum {
+            self.request_at_quorum(call).await
+        } else {
+            self.request(|provider| call(provider, BlockId::Number(BlockNumber::Latest))).await
+        }
+    }
+
+    /// Try the endpoints in order until one of them answers within the timeout
+    pub async fn request<T, F, Fut>(&self, call: F) -> Result<T, NodeError>
+    where
+        F: Fn(Arc<Provider<Http>>) -> Fut,
+        Fut: Future<Output = NodeResult<T>>
+    {
+        for endpoint in &self.endpoints {
+            if let Some(result) = self.with_timeout(endpoint, call(endpoint.provider.clone())).await {
+                return Ok(result)
+            }
+        }
+        Err(NodeError::AllEndpointsFailed(self.chain_id))
+    }
+
+    pub async fn request_at_quorum<T, F, Fut>(&self, call: F) -> Result<T, NodeError>
+    where
+        T: PartialEq,
+        F: Fn(Arc<Provider<Http>>, BlockId) -> Fut,
+        Fut: Future<Output = NodeResult<T>>
+    {
+        // The lowest latest block, so the lagging endpoints can answer as well
+        let block_numbers = join_all(self.endpoints.iter().map(|endpoint| {
+            let provider = endpoint.provider.clone();
+            self.with_timeout(endpoint, async move { Ok(provider.get_block_number().await?) })
+        })).await;
+        let block_number = block_numbers.into_iter()
+            .flatten()
+            .min()
+            .ok_or(NodeError::AllEndpointsFailed(self.chain_id))?;
+
+        let block = BlockId::Number(BlockNumber::Number(block_number));
+        let results = join_all(
+            self.endpoints.iter().map(|endpoint| self.with_timeout(endpoint, call(endpoint.provider.clone(), block)))
+        ).await;
+
+        find_quorum(results, self.endpoints.len())
+            .ok_or(NodeError::NoQuorum(self.chain_id, block_number.as_u64()))
+    }
+
+    async fn with_timeout<T>(&self, endpoint: &NodeEndpoint, future: impl Future<Output = NodeResult<T>>) -> Option<T> {
+        match timeout(self.timeout, future).await {
+            Ok(Ok(result)) => Some(result),
+            Ok(Err(e)) => {
+                eprintln!("[Node] {} of chain id {} failed: {e}", endpoint.name, self.chain_id);
+                None
+            }
+            Err(_) => {
+                eprintln!("[Node] {} of chain id {} timed out after {:?}", endpoint.name, self.chain_id, self.timeout);
+                None
+            }
+        }
+    }
+}
+
+/// The value returned by more than half of `total` endpoints, failed endpoints count as disagreeing
+pub fn find_quorum<T: PartialEq>(results: Vec<Option<T>>, total: usize) -> Option<T> {
+    let values: Vec<T> = results.into_iter().flatten().collect();
+    let majority = values.iter()
+        .position(|value| values.iter().filter(|other| *other == value).count() * 2 > total)?;
+    values.into_iter().nth(majority)
 }
@@ -0,0 +1,65 @@
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::blockchain::solana::dto::{ContextValue, RpcResponse, TokenAccount};
+use crate::utils::error::HttpError;
+use crate::utils::http_client::HttpClient;
+
+pub struct SolanaClient {
+    pub rpc_url: String,
+    client: HttpClient
+}
+
+impl SolanaClient {
+    pub fn new(rpc_url: String) -> Self {
+        Self { rpc_url, client: HttpClient::new("Solana".to_string()) }
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: Value) -> Result<T, HttpError> {
+        let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = self.client.post_json(self.rpc_url.clone(), &body).await?;
+        if !response.status().is_success() {
+            eprintln!("[Solana] {method} failed: {:?}", response.status());
+            return Err(HttpError::ResponseError)
+        }
+
+        let data = response.json::<RpcResponse<T>>().await.map_err(|_| HttpError::ResponseDataError)?;
+        match (data.result, data.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => {
+                eprintln!("[Solana] {method} error {}: {}", error.code, error.message);
+                Err(HttpError::ResponseDataError)
+            }
+            (None, None) => Err(HttpError::ResponseDataError)
+        }
+    }
+
+    /// Balance in lamports
+    pub async fn fetch_balance(&self, address: &str) -> Result<u64, HttpError> {
+        let balance = self.call::<ContextValue<u64>>(
+            "getBalance",
+            json!([address, { "commitment": "confirmed" }])
+        ).await?;
+        Ok(balance.value)
+    }
+
+    /// Raw amount summed over every token account of the owner and the decimals of the mint,
+    /// the decimals are `None` if the owner has no token account
+    pub async fn fetch_token_balance(&self, owner: &str, mint: &str) -> Result<(u64, Option<u8>), HttpError> {
+        let token_accounts = self.call::<ContextValue<Vec<TokenAccount>>>(
+            "getTokenAccountsByOwner",
+            json!([owner, { "mint": mint }, { "encoding": "jsonParsed", "commitment": "confirmed" }])
+        ).await?;
+
+        let mut amount: u64 = 0;
+        let mut decimals = None;
+        for token_account in token_accounts.value {
+            let info = token_account.account.data.parsed.info;
+            let token_amount = info.token_amount;
+            println!("[Solana] Token account {} of mint {} holds {}", token_account.pubkey, info.mint, token_amount.amount);
+            amount += token_amount.amount.parse::<u64>().map_err(|_| HttpError::ResponseDataError)?;
+            decimals = Some(token_amount.decimals);
+        }
+        Ok((amount, decimals))
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Deserialize;
+
+pub const SOL_DECIMALS: u8 = 9;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<RpcError>
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String
+}
+
+/// Most results are wrapped with the slot they are read at
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContextValue<T> {
+    pub value: T
+}
+
+/// https://solana.com/docs/rpc/http/gettokenaccountsbyowner with `jsonParsed` encoding
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccount {
+    pub pubkey: String,
+    pub account: TokenAccountData
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccountData {
+    pub data: ParsedAccountData
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedAccountData {
+    pub parsed: ParsedTokenAccount
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedTokenAccount {
+    pub info: TokenAccountInfo
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccountInfo {
+    pub mint: String,
+    pub token_amount: TokenAmount
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAmount {
+    /// Raw amount without decimals
+    pub amount: String,
+    pub decimals: u8
+}
+
+/// Convert lamports or raw token amounts
+pub fn to_ui_amount(amount: u64, decimals: u8) -> f64 {
+    amount as f64 / 10f64.powi(decimals as i32)
+}
@@ -0,0 +1,2 @@
+pub mod client;
+pub mod dto;
\ No newline at end of file
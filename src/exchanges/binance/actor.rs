@@ -0,0 +1,263 @@
+use std::env;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::exchanges::dto::{AssetBalance, FundingInfo, NetworkInfo, OrderBook, PriceResult, DEFAULT_FUNDING_HOURS};
+use crate::exchanges::exchange::Exchange;
+use crate::exchanges::symbol::{InstrumentInfo, MarketType};
+use crate::exchanges::binance::{
+    connector::BinanceConnector,
+    dto::{
+        AccountInfo, AllCcyInfo, Depth, ExchangeInfo, FundingIntervalInfo, FuturesExchangeInfo, PremiumIndex,
+        SymbolPriceTicker
+    }
+};
+use crate::utils::config_struct::{Exchanges, Instruments};
+use crate::utils::error::HttpError;
+use crate::utils::number_utils::{calculate_effective_prices, calculate_price_with_trading_fee, parse_decimal};
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BinanceActor {
+    /// BINANCE_API_KEY, only the account and currency apis need it
+    pub api_key: Option<String>,
+    /// BINANCE_SECRET_KEY
+    pub secret_key: Option<String>,
+
+    pub data_source: String
+}
+
+#[cfg_attr(test, automock)]
+impl BinanceActor {
+    pub fn new() -> Self {
+        let api_key = env::var("BINANCE_API_KEY").ok();
+        let secret_key = env::var("BINANCE_SECRET_KEY").ok();
+        let data_source = "Binance".to_string();
+
+        Self { api_key, secret_key, data_source }
+    }
+
+    /// Connector of the public market data apis, which work without the api keys
+    fn public_connector(&self) -> BinanceConnector {
+        BinanceConnector::new(self.api_key.clone().unwrap_or_default(), self.secret_key.clone().unwrap_or_default())
+    }
+
+    /// Connector of the signed apis, it fails if the api keys are not set
+    fn signed_connector(&self) -> Result<BinanceConnector, HttpError> {
+        match (&self.api_key, &self.secret_key) {
+            (Some(api_key), Some(secret_key)) => Ok(BinanceConnector::new(api_key.clone(), secret_key.clone())),
+            _ => Err(HttpError::MissingApiKey("BINANCE_API_KEY and BINANCE_SECRET_KEY".to_string()))
+        }
+    }
+
+    pub async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        let data_source = self.data_source.clone();
+        let target_ccy = instruments.target_ccy.to_ascii_uppercase();
+        let inst_id = symbol.symbol.clone();
+        let (url, api_prefix) = market_api(symbol.market_type, &exchange_config)?;
+
+        let uri = format!("{api_prefix}/ticker/price");
+        let parameters = format!("symbol={inst_id}");
+
+        let binance = self.public_connector();
+        let data = binance.http_client::<SymbolPriceTicker>(url, uri, parameters, false).await?;
+
+        let original_price = data.price;
+        let price = calculate_price_with_trading_fee(
+            data_source.clone(),
+            original_price.clone(),
+            exchange_config.trading_fee_rate
+        )?;
+
+        let order_book = self.fetch_order_book(symbol.clone(), exchange_config.clone()).await?;
+        let (buy_price, sell_price) = calculate_effective_prices(
+            data_source.clone(), &order_book, instruments.notional_size
+        );
+
+        println!(
+            "[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}] [Buy: {:?}] [Sell: {:?}]",
+            buy_price, sell_price
+        );
+        Ok(PriceResult {
+            data_source,
+            instrument: inst_id,
+            market_type: symbol.market_type,
+            quote_ccy: symbol.base_ccy,
+            price,
+            best_bid: order_book.best_bid(),
+            best_ask: order_book.best_ask(),
+            buy_price,
+            sell_price,
+            trading_fee_rate: exchange_config.trading_fee_rate,
+            normalization: None
+        })
+    }
+
+    /// Spot pairs, and USDⓈ-M perpetuals and futures if `derivatives_url` is configured.
+    /// The funding intervals of the perpetuals are loaded here once instead of on every funding request.
+    pub async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        let data_source = self.data_source.clone();
+        let uri = "/api/v3/exchangeInfo".to_string();
+
+        let binance = self.public_connector();
+        let data = binance.http_client::<ExchangeInfo>(exchange_config.url.clone(), uri, "".to_string(), false).await?;
+
+        let mut instruments: Vec<InstrumentInfo> = data.symbols.iter()
+            .filter(|item| item.status == "TRADING")
+            .map(|item| InstrumentInfo::new(&item.symbol, &item.base_asset, &item.quote_asset, MarketType::Spot))
+            .collect();
+
+        if let Some(derivatives_url) = exchange_config.derivatives_url {
+            // Only the perpetuals with an adjusted interval are listed, the others fund every DEFAULT_FUNDING_HOURS
+            let uri = "/fapi/v1/fundingInfo".to_string();
+            let funding_intervals = binance.http_client::<Vec<FundingIntervalInfo>>(derivatives_url.clone(), uri, "".to_string(), false).await
+                .unwrap_or_else(|e| {
+                    eprintln!("[{data_source}] Failed to fetch the funding intervals: {e}");
+                    vec!()
+                });
+
+            let uri = "/fapi/v1/exchangeInfo".to_string();
+            match binance.http_client::<FuturesExchangeInfo>(derivatives_url, uri, "".to_string(), false).await {
+                Ok(data) => instruments.extend(data.symbols.iter()
+                    .filter(|item| item.status == "TRADING")
+                    .filter_map(|item| {
+                        let market_type = match item.contract_type.as_str() {
+                            "PERPETUAL" => MarketType::Perp,
+                            "CURRENT_QUARTER" | "NEXT_QUARTER" => MarketType::Futures,
+                            _ => return None
+                        };
+                        let funding_interval_hours = funding_intervals.iter()
+                            .find(|funding| funding.symbol == item.symbol)
+                            .map(|funding| funding.funding_interval_hours)
+                            .filter(|_| market_type == MarketType::Perp);
+                        Some(InstrumentInfo {
+                            expiry: Some(item.delivery_date).filter(|_| market_type == MarketType::Futures),
+                            funding_interval_hours,
+                            ..InstrumentInfo::new(&item.symbol, &item.base_asset, &item.quote_asset, market_type)
+                        })
+                    })
+                ),
+                Err(e) => eprintln!("[{data_source}] Failed to fetch the futures instruments: {e}")
+            }
+        }
+
+        Ok(instruments)
+    }
+
+    pub async fn fetch_order_book(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<OrderBook, HttpError> {
+        let (url, api_prefix) = market_api(symbol.market_type, &exchange_config)?;
+        let uri = format!("{api_prefix}/depth");
+        let parameters = format!("symbol={}&limit=1000", symbol.symbol);
+
+        let binance = self.public_connector();
+        let data = binance.http_client::<Depth>(url, uri, parameters, false).await?;
+
+        // Quantities of USDⓈ-M contracts are in the base asset as well
+        OrderBook::from_raw_levels(data.bids, data.asks, Decimal::ONE)
+    }
+
+    pub async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        let data_source = self.data_source.clone();
+        let url = exchange_config.derivatives_url.ok_or(HttpError::Unsupported)?;
+        let binance = self.public_connector();
+
+        let uri = "/fapi/v1/premiumIndex".to_string();
+        let parameters = format!("symbol={}", symbol.symbol);
+        let data = binance.http_client::<PremiumIndex>(url, uri, parameters, false).await?;
+
+        let interval_hours = symbol.funding_interval_hours.unwrap_or(DEFAULT_FUNDING_HOURS);
+
+        Ok(FundingInfo {
+            data_source,
+            instrument: symbol.symbol,
+            funding_rate: parse_decimal(&data.last_funding_rate).ok_or(HttpError::ResponseDataError)?,
+            predicted_funding_rate: None,
+            interval_hours: Decimal::from(interval_hours),
+            next_funding_time: Some(data.next_funding_time),
+            mark_price: parse_decimal(&data.mark_price),
+            index_price: parse_decimal(&data.index_price)
+        })
+    }
+
+    pub async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        let data_source = self.data_source.clone();
+        let target_ccy = instruments.target_ccy.to_ascii_uppercase();
+        let uri = "/sapi/v1/capital/config/getall".to_string();
+
+        let binance = self.signed_connector()?;
+        let data = binance.http_client::<Vec<AllCcyInfo>>(exchange_config.url.clone(), uri, "".to_string(), true).await?;
+
+        let coin_config_list = data.iter().find(|item| item.coin == target_ccy).ok_or(HttpError::ResponseDataError)?;
+        let coin_config = coin_config_list.network_list.iter().find(
+            |item| item.name.to_ascii_uppercase().contains(instruments.withdrawal_chain.to_ascii_uppercase().as_str())
+                || item.network.to_ascii_uppercase().contains(instruments.withdrawal_chain.to_ascii_uppercase().as_str())
+        ).ok_or(HttpError::ResponseDataError)?;
+
+        println!("[{data_source}] CCY Data: {:?}", coin_config);
+
+        Ok(NetworkInfo {
+            data_source,
+            chain: coin_config.network.clone(),
+            withdraw_enable: coin_config.withdraw_enable,
+            deposit_enable: coin_config.deposit_enable,
+            withdraw_fee: parse_decimal(&coin_config.withdraw_fee).ok_or(HttpError::ResponseDataError)?
+        })
+    }
+
+    pub async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        let data_source = self.data_source.clone();
+        let uri = "/api/v3/account".to_string();
+        let parameters = "omitZeroBalances=true".to_string();
+
+        let binance = self.signed_connector()?;
+        let data = binance.http_client::<AccountInfo>(exchange_config.url.clone(), uri, parameters, true).await?;
+        println!("[{data_source}] Account can trade: {}, balances: {:?}", data.can_trade, data.balances);
+
+        Ok(())
+    }
+}
+
+/// Base url and path prefix of the market data api, derivatives are on the USDⓈ-M futures api
+fn market_api(market_type: MarketType, exchange_config: &Exchanges) -> Result<(String, &'static str), HttpError> {
+    match market_type {
+        MarketType::Spot => Ok((exchange_config.url.clone(), "/api/v3")),
+        MarketType::Perp | MarketType::Futures => exchange_config.derivatives_url.clone()
+            .map(|url| (url, "/fapi/v1"))
+            .ok_or(HttpError::Unsupported)
+    }
+}
+
+#[async_trait]
+impl Exchange for BinanceActor {
+    fn data_source(&self) -> String {
+        self.data_source.clone()
+    }
+
+    async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        BinanceActor::fetch_instruments(self, exchange_config).await
+    }
+
+    async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        BinanceActor::fetch_price(self, instruments, symbol, exchange_config).await
+    }
+
+    async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        BinanceActor::fetch_funding(self, symbol, exchange_config).await
+    }
+
+    async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        BinanceActor::fetch_ccy_info(self, instruments, exchange_config).await
+    }
+
+    async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        BinanceActor::fetch_account(self, exchange_config).await
+    }
+
+    async fn fetch_balances(&self, _exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        eprintln!("[{}] Account balances are not supported yet", self.data_source);
+        Err(HttpError::Unsupported)
+    }
+}
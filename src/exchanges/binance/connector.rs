@@ -0,0 +1,100 @@
+use reqwest::header::HeaderMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::exchanges::signer::{sign, hex_encode};
+use crate::utils::error::{HttpError, SignError};
+use crate::utils::http_client::HttpClient;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Signature {
+    /// Signature.
+    pub signature: String,
+
+    /// Timestamp.
+    pub timestamp: String,
+
+    /// URI with timestamp and signature
+    pub full_uri: String,
+}
+
+/// The APIKey definition of Binance.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BinanceConnector {
+    /// BINANCE_API_KEY
+    pub api_key: String,
+    /// BINANCE_SECRET_KEY
+    pub secret_key: String,
+
+    pub data_source: String,
+}
+
+impl BinanceConnector {
+    pub fn new(api_key: String, secret_key: String) -> Self {
+        let data_source = "Binance".to_string();
+        Self { api_key, secret_key, data_source }
+    }
+
+    // Reference: https://binance-docs.github.io/apidocs/spot/en/#signed-trade-user_data-and-margin-endpoint-security
+    pub fn sign(&self, uri: String, parameters: String) -> Result<Signature, SignError> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_millis().to_string();
+        let total_parameters: String = if parameters.is_empty() {
+            "timestamp=".to_string() + timestamp.as_str()
+        } else {
+            parameters + "&timestamp=" + timestamp.as_str()
+        };
+
+        let encoded_sign = self.sign_query(total_parameters.clone())?;
+        let full_uri = uri + "?" + total_parameters.as_str() + "&signature=" + encoded_sign.as_str();
+
+        Ok(Signature { signature: encoded_sign, timestamp, full_uri })
+    }
+
+    /// HMAC SHA256 of the total query string, hex encoded
+    pub fn sign_query(&self, total_parameters: String) -> Result<String, SignError> {
+        let raw_sign = sign(total_parameters, self.secret_key.clone())?;
+        Ok(hex_encode(raw_sign))
+    }
+
+    pub fn build_headers(&self) -> Result<HeaderMap, ()> {
+        let mut headers = HeaderMap::new();
+        // The public apis are called without api keys
+        if !self.api_key.is_empty() {
+            headers.insert("X-MBX-APIKEY", self.api_key.parse().unwrap());
+        }
+        headers.insert("Content-Type", "application/json".parse().unwrap());
+        Ok(headers)
+    }
+
+    pub async fn http_client<T>(&self, url: String, uri: String, parameters: String, need_sign: bool) -> Result<T, HttpError>
+        where
+            T: serde::de::DeserializeOwned + Clone {
+        let data_source = self.data_source.clone();
+        let headers = self.build_headers().unwrap();
+
+        let client = HttpClient::new(data_source.clone());
+
+        let final_uri: String = if need_sign {
+            self.sign(uri, parameters)
+                .unwrap_or_else(|_| panic!("Failed to create {} signature", data_source))
+                .full_uri
+        } else if parameters.is_empty() {
+            uri
+        } else {
+            uri + "?" + &*parameters
+        };
+
+        let response = client.send_request(url, final_uri, headers).await?;
+
+        if response.status().is_success() {
+            let parsed_response = response
+                .json::<T>()
+                .await
+                .unwrap_or_else(|_| panic!("[{}] Failed to deserialize response", data_source));
+            Ok(parsed_response)
+        } else {
+            eprintln!("[{data_source}] Response Error {:?}", response);
+            Err(HttpError::ResponseError)
+        }
+    }
+}
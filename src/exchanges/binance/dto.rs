@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+/// Reference: https://binance-docs.github.io/apidocs/spot/en/#symbol-price-ticker
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SymbolPriceTicker {
+    pub symbol: String,
+    pub price: String,
+}
+
+/// Reference: https://binance-docs.github.io/apidocs/spot/en/#order-book
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Depth {
+    #[serde(rename = "lastUpdateId")]
+    pub last_update_id: i64,
+    pub bids: Vec<Vec<String>>,
+    pub asks: Vec<Vec<String>>
+}
+
+/// Reference: https://binance-docs.github.io/apidocs/spot/en/#all-coins-39-information-user_data
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AllCcyInfo {
+    pub coin: String,
+    pub name: String,
+    #[serde(rename = "networkList")]
+    pub network_list: Vec<CcyInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CcyInfo {
+    pub network: String,
+    pub name: String,
+    #[serde(rename = "depositEnable")]
+    pub deposit_enable: bool,
+    #[serde(rename = "withdrawEnable")]
+    pub withdraw_enable: bool,
+    #[serde(rename = "withdrawFee")]
+    pub withdraw_fee: String,
+    #[serde(rename = "withdrawMax")]
+    pub withdraw_max: String,
+    #[serde(rename = "withdrawMin")]
+    pub withdraw_min: String,
+    #[serde(rename = "minConfirm")]
+    pub min_confirm: i32
+}
+
+/// Reference: https://binance-docs.github.io/apidocs/spot/en/#account-information-user_data
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountInfo {
+    #[serde(rename = "canTrade")]
+    pub can_trade: bool,
+    pub balances: Vec<Balance>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Balance {
+    pub asset: String,
+    pub free: String,
+    pub locked: String
+}
+
+/// Reference: https://binance-docs.github.io/apidocs/spot/en/#exchange-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExchangeInfo {
+    pub symbols: Vec<SymbolInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    /// `TRADING` when it can be traded
+    pub status: String,
+    #[serde(rename = "baseAsset")]
+    pub base_asset: String,
+    #[serde(rename = "quoteAsset")]
+    pub quote_asset: String
+}
+
+/// Reference: https://binance-docs.github.io/apidocs/futures/en/#exchange-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FuturesExchangeInfo {
+    pub symbols: Vec<FuturesSymbolInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FuturesSymbolInfo {
+    pub symbol: String,
+    /// `TRADING` when it can be traded
+    pub status: String,
+    #[serde(rename = "baseAsset")]
+    pub base_asset: String,
+    #[serde(rename = "quoteAsset")]
+    pub quote_asset: String,
+    /// `PERPETUAL`, `CURRENT_QUARTER` or `NEXT_QUARTER`
+    #[serde(rename = "contractType")]
+    pub contract_type: String,
+    /// In milliseconds
+    #[serde(rename = "deliveryDate")]
+    pub delivery_date: i64
+}
+
+/// Reference: https://binance-docs.github.io/apidocs/futures/en/#mark-price
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PremiumIndex {
+    pub symbol: String,
+    #[serde(rename = "markPrice")]
+    pub mark_price: String,
+    #[serde(rename = "indexPrice")]
+    pub index_price: String,
+    #[serde(rename = "lastFundingRate")]
+    pub last_funding_rate: String,
+    /// In milliseconds
+    #[serde(rename = "nextFundingTime")]
+    pub next_funding_time: i64
+}
+
+/// Only the symbols with an adjusted funding interval are listed
+/// Reference: https://binance-docs.github.io/apidocs/futures/en/#get-funding-rate-info
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundingIntervalInfo {
+    pub symbol: String,
+    #[serde(rename = "fundingIntervalHours")]
+    pub funding_interval_hours: i64
+}
@@ -0,0 +1,242 @@
+use std::env;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::exchanges::dto::{AssetBalance, FundingInfo, NetworkInfo, OrderBook, PriceResult, DEFAULT_FUNDING_HOURS};
+use crate::exchanges::exchange::Exchange;
+use crate::exchanges::symbol::{InstrumentInfo, MarketType};
+use crate::exchanges::bybit::{
+    connector::BybitConnector,
+    dto::{Category, CoinInfoResult, InstrumentInfoResult, ListResult, OrderBookResult, Ticker, WalletBalance}
+};
+use crate::utils::config_struct::{Exchanges, Instruments};
+use crate::utils::error::HttpError;
+use crate::utils::number_utils::{calculate_effective_prices, calculate_price_with_trading_fee, parse_decimal};
+
+#[cfg(test)]
+use mockall::{automock, predicate::*};
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BybitActor {
+    /// BYBIT_API_KEY, only the account and currency apis need it
+    pub api_key: Option<String>,
+    /// BYBIT_SECRET_KEY
+    pub secret_key: Option<String>,
+
+    pub data_source: String
+}
+
+#[cfg_attr(test, automock)]
+impl BybitActor {
+    pub fn new() -> Self {
+        let api_key = env::var("BYBIT_API_KEY").ok();
+        let secret_key = env::var("BYBIT_SECRET_KEY").ok();
+        let data_source = "Bybit".to_string();
+
+        Self { api_key, secret_key, data_source }
+    }
+
+    /// Connector of the public market data apis, which work without the api keys
+    fn public_connector(&self) -> BybitConnector {
+        BybitConnector::new(self.api_key.clone().unwrap_or_default(), self.secret_key.clone().unwrap_or_default())
+    }
+
+    /// Connector of the signed apis, it fails if the api keys are not set
+    fn signed_connector(&self) -> Result<BybitConnector, HttpError> {
+        match (&self.api_key, &self.secret_key) {
+            (Some(api_key), Some(secret_key)) => Ok(BybitConnector::new(api_key.clone(), secret_key.clone())),
+            _ => Err(HttpError::MissingApiKey("BYBIT_API_KEY and BYBIT_SECRET_KEY".to_string()))
+        }
+    }
+
+    pub async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        let data_source = self.data_source.clone();
+        let target_ccy = instruments.target_ccy.to_ascii_uppercase();
+        let category = Category::of(symbol.market_type);
+
+        let ticker = self.fetch_ticker(category, symbol.symbol, exchange_config.clone()).await?;
+        let mark_price = ticker.mark_price.clone().unwrap_or("-".to_string());
+
+        let original_price = ticker.last_price;
+        let price = calculate_price_with_trading_fee(
+            data_source.clone(),
+            original_price.clone(),
+            exchange_config.trading_fee_rate
+        )?;
+
+        let order_book = self.fetch_order_book(category, ticker.symbol.clone(), exchange_config.clone()).await?;
+        let (buy_price, sell_price) = calculate_effective_prices(
+            data_source.clone(), &order_book, instruments.notional_size
+        );
+
+        println!(
+            "[{data_source}] {target_ccy} price: [Original: {original_price}] [Mark: {mark_price}] [With trading fee: {price}] [Buy: {:?}] [Sell: {:?}]",
+            buy_price, sell_price
+        );
+        Ok(PriceResult {
+            data_source,
+            instrument: ticker.symbol,
+            market_type: symbol.market_type,
+            quote_ccy: symbol.base_ccy,
+            price,
+            best_bid: order_book.best_bid(),
+            best_ask: order_book.best_ask(),
+            buy_price,
+            sell_price,
+            trading_fee_rate: exchange_config.trading_fee_rate,
+            normalization: None
+        })
+    }
+
+    pub async fn fetch_order_book(&self, category: Category, inst_id: String, exchange_config: Exchanges) -> Result<OrderBook, HttpError> {
+        let uri = "/v5/market/orderbook".to_string();
+        let parameters = format!("category={}&symbol={inst_id}&limit=200", category.as_str());
+
+        let bybit = self.public_connector();
+        let data = bybit.http_client::<OrderBookResult>(exchange_config.url, uri, parameters, false).await?;
+
+        OrderBook::from_raw_levels(data.b, data.a, Decimal::ONE)
+    }
+
+    /// Spot and linear perpetual share the same ticker endpoint, only the category differs
+    pub async fn fetch_ticker(&self, category: Category, inst_id: String, exchange_config: Exchanges) -> Result<Ticker, HttpError> {
+        let uri = "/v5/market/tickers".to_string();
+        let parameters = format!("category={}&symbol={inst_id}", category.as_str());
+
+        let bybit = self.public_connector();
+        let data = bybit.http_client::<ListResult<Ticker>>(exchange_config.url, uri, parameters, false).await?;
+
+        data.list.first().cloned().ok_or(HttpError::ResponseDataError)
+    }
+
+    /// Spot pairs, linear perpetuals and linear futures that are trading, 1000 is the page limit and more than the listed contracts.
+    /// A category that fails to load is skipped.
+    pub async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        let uri = "/v5/market/instruments-info".to_string();
+        let bybit = self.public_connector();
+        let mut instruments = Vec::new();
+        let mut last_error = None;
+
+        for category in [Category::Spot, Category::Linear] {
+            let parameters = format!("category={}&limit=1000", category.as_str());
+            let data = match bybit.http_client::<ListResult<InstrumentInfoResult>>(exchange_config.url.clone(), uri.clone(), parameters, false).await {
+                Ok(data) => data,
+                Err(e) => {
+                    eprintln!("[{}] Failed to fetch {} instruments, skipped: {e}", self.data_source, category.as_str());
+                    last_error = Some(e);
+                    continue
+                }
+            };
+
+            instruments.extend(data.list.iter()
+                .filter(|item| item.status == "Trading")
+                .filter_map(|item| {
+                    let market_type = match (category, item.contract_type.as_deref()) {
+                        (Category::Spot, _) => MarketType::Spot,
+                        (Category::Linear, Some("LinearPerpetual")) => MarketType::Perp,
+                        (Category::Linear, Some("LinearFutures")) => MarketType::Futures,
+                        _ => return None
+                    };
+                    Some(InstrumentInfo {
+                        expiry: item.delivery_time.as_deref().and_then(|time| time.parse::<i64>().ok()).filter(|time| *time > 0),
+                        // Bybit publishes the funding interval in minutes
+                        funding_interval_hours: item.funding_interval.map(|minutes| minutes / 60).filter(|hours| *hours > 0),
+                        ..InstrumentInfo::new(&item.symbol, &item.base_coin, &item.quote_coin, market_type)
+                    })
+                })
+            );
+        }
+
+        match last_error {
+            Some(e) if instruments.is_empty() => Err(e),
+            _ => Ok(instruments)
+        }
+    }
+
+    pub async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        let data_source = self.data_source.clone();
+        let ticker = self.fetch_ticker(Category::Linear, symbol.symbol.clone(), exchange_config).await?;
+        let interval_hours = Decimal::from(symbol.funding_interval_hours.unwrap_or(DEFAULT_FUNDING_HOURS));
+
+        let funding_rate = ticker.funding_rate.as_deref().and_then(parse_decimal).ok_or(HttpError::ResponseDataError)?;
+        Ok(FundingInfo {
+            data_source,
+            instrument: ticker.symbol,
+            funding_rate,
+            predicted_funding_rate: None,
+            interval_hours,
+            next_funding_time: ticker.next_funding_time.and_then(|time| time.parse::<i64>().ok()),
+            mark_price: ticker.mark_price.as_deref().and_then(parse_decimal),
+            index_price: ticker.index_price.as_deref().and_then(parse_decimal)
+        })
+    }
+
+    pub async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        let data_source = self.data_source.clone();
+        let target_ccy = instruments.target_ccy.to_ascii_uppercase();
+        let uri = "/v5/asset/coin/query-info".to_string();
+        let parameters = format!("coin={target_ccy}");
+
+        let bybit = self.signed_connector()?;
+        let data = bybit.http_client::<CoinInfoResult>(exchange_config.url.clone(), uri, parameters, true).await?;
+
+        let coin_info = data.rows.iter().find(|item| item.coin == target_ccy).ok_or(HttpError::ResponseDataError)?;
+        let chain_info = coin_info.chains.iter().find(|item| item.is_network(&instruments.withdrawal_chain))
+            .ok_or(HttpError::UnlistedInstrument(format!("{target_ccy} on {}", instruments.withdrawal_chain)))?;
+
+        println!("[{data_source}] CCY Data: {:?}", chain_info);
+
+        Ok(NetworkInfo {
+            data_source,
+            chain: chain_info.chain.clone(),
+            withdraw_enable: chain_info.chain_withdraw == "1",
+            deposit_enable: chain_info.chain_deposit == "1",
+            withdraw_fee: parse_decimal(&chain_info.withdraw_fee).ok_or(HttpError::ResponseDataError)?
+        })
+    }
+
+    pub async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        let data_source = self.data_source.clone();
+        let uri = "/v5/account/wallet-balance".to_string();
+        let parameters = "accountType=UNIFIED".to_string();
+
+        let bybit = self.signed_connector()?;
+        let data = bybit.http_client::<ListResult<WalletBalance>>(exchange_config.url.clone(), uri, parameters, true).await?;
+        println!("[{data_source}] Wallet balance: {:?}", data.list);
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Exchange for BybitActor {
+    fn data_source(&self) -> String {
+        self.data_source.clone()
+    }
+
+    async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        BybitActor::fetch_instruments(self, exchange_config).await
+    }
+
+    async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        BybitActor::fetch_price(self, instruments, symbol, exchange_config).await
+    }
+
+    async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        BybitActor::fetch_funding(self, symbol, exchange_config).await
+    }
+
+    async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        BybitActor::fetch_ccy_info(self, instruments, exchange_config).await
+    }
+
+    async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        BybitActor::fetch_account(self, exchange_config).await
+    }
+
+    async fn fetch_balances(&self, _exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        eprintln!("[{}] Account balances are not supported yet", self.data_source);
+        Err(HttpError::Unsupported)
+    }
+}
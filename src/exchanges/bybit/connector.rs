@@ -0,0 +1,98 @@
+use reqwest::header::HeaderMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
+
+use crate::exchanges::bybit::dto::ApiResponse;
+use crate::exchanges::signer::{sign, hex_encode};
+use crate::utils::error::{HttpError, SignError};
+use crate::utils::http_client::HttpClient;
+
+const RECV_WINDOW: &str = "5000";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Signature {
+    /// Signature.
+    pub signature: String,
+
+    /// Timestamp.
+    pub timestamp: String
+}
+
+/// The APIKey definition of Bybit.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BybitConnector {
+    /// BYBIT_API_KEY
+    pub api_key: String,
+    /// BYBIT_SECRET_KEY
+    pub secret_key: String,
+
+    pub data_source: String,
+}
+
+impl BybitConnector {
+    pub fn new(api_key: String, secret_key: String) -> Self {
+        let data_source = "Bybit".to_string();
+        Self { api_key, secret_key, data_source }
+    }
+
+    // Reference: https://bybit-exchange.github.io/docs/v5/guide#create-a-request
+    pub fn sign(&self, parameters: &str, timestamp: String) -> Result<Signature, SignError> {
+        let raw_sign = timestamp.clone() + self.api_key.as_str() + RECV_WINDOW + parameters;
+        let raw_sign = sign(raw_sign, self.secret_key.clone())?;
+
+        Ok(Signature { signature: hex_encode(raw_sign), timestamp })
+    }
+
+    pub fn build_headers(&self, signature: Option<Signature>) -> Result<HeaderMap, ()> {
+        let mut headers = HeaderMap::new();
+        if let Some(signature) = signature {
+            headers.insert("X-BAPI-API-KEY", self.api_key.parse().unwrap());
+            headers.insert("X-BAPI-SIGN", signature.signature.parse().unwrap());
+            headers.insert("X-BAPI-SIGN-TYPE", "2".parse().unwrap());
+            headers.insert("X-BAPI-TIMESTAMP", signature.timestamp.parse().unwrap());
+            headers.insert("X-BAPI-RECV-WINDOW", RECV_WINDOW.parse().unwrap());
+        }
+        headers.insert("Content-Type", "application/json".parse().unwrap());
+        Ok(headers)
+    }
+
+    pub async fn http_client<T>(&self, url: String, uri: String, parameters: String, need_sign: bool) -> Result<T, HttpError>
+        where
+            T: serde::de::DeserializeOwned + Clone {
+        let data_source = self.data_source.clone();
+
+        let signature = if need_sign {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards").as_millis().to_string();
+            let signature = self.sign(parameters.as_str(), timestamp)
+                .unwrap_or_else(|_| panic!("Failed to create {} signature", data_source));
+            Some(signature)
+        } else {
+            None
+        };
+        let headers = self.build_headers(signature).unwrap();
+
+        let client = HttpClient::new(data_source.clone());
+        let final_uri = if parameters.is_empty() { uri } else { uri + "?" + &*parameters };
+        let response = client.send_request(url, final_uri, headers).await?;
+
+        if response.status().is_success() {
+            let parsed_response = response
+                .json::<ApiResponse>()
+                .await
+                .unwrap_or_else(|_| panic!("[{}] Failed to deserialize response", data_source));
+
+            if parsed_response.ret_code == 0 {
+                serde_json::from_value::<T>(parsed_response.result).map_err(|error| {
+                    eprintln!("[{data_source}] Failed to deserialize result: {error}");
+                    HttpError::ResponseDataError
+                })
+            } else {
+                eprintln!("[{data_source}] {} {:?}", parsed_response.ret_code, parsed_response.ret_msg);
+                Err(HttpError::ResponseDataError)
+            }
+        } else {
+            eprintln!("[{data_source}] Response Error {:?}", response);
+            Err(HttpError::ResponseError)
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::exchanges::symbol::MarketType;
+
+/// The `result` is an empty object when `retCode` is not 0, so it is parsed later
+/// Reference: https://bybit-exchange.github.io/docs/v5/error
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiResponse {
+    #[serde(rename = "retCode")]
+    pub ret_code: i32,
+    #[serde(rename = "retMsg")]
+    pub ret_msg: String,
+    pub result: Value
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ListResult<T> {
+    pub list: Vec<T>
+}
+
+/// Product type of Bybit v5 market endpoints
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Category {
+    Spot,
+    /// USDT and USDC perpetuals and futures
+    Linear
+}
+
+impl Category {
+    pub fn of(market_type: MarketType) -> Self {
+        match market_type {
+            MarketType::Spot => Category::Spot,
+            MarketType::Perp | MarketType::Futures => Category::Linear
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Category::Spot => "spot",
+            Category::Linear => "linear"
+        }
+    }
+}
+
+/// Reference: https://bybit-exchange.github.io/docs/v5/market/tickers
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Ticker {
+    pub symbol: String,
+    #[serde(rename = "lastPrice")]
+    pub last_price: String,
+    #[serde(rename = "bid1Price")]
+    pub bid1_price: String,
+    #[serde(rename = "bid1Size")]
+    pub bid1_size: String,
+    #[serde(rename = "ask1Price")]
+    pub ask1_price: String,
+    #[serde(rename = "ask1Size")]
+    pub ask1_size: String,
+    /// Only for linear
+    #[serde(rename = "markPrice")]
+    pub mark_price: Option<String>,
+    /// Only for linear
+    #[serde(rename = "indexPrice")]
+    pub index_price: Option<String>,
+    /// Only for linear
+    #[serde(rename = "fundingRate")]
+    pub funding_rate: Option<String>,
+    /// Only for linear, in milliseconds
+    #[serde(rename = "nextFundingTime")]
+    pub next_funding_time: Option<String>
+}
+
+/// Reference: https://bybit-exchange.github.io/docs/v5/market/instrument
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstrumentInfoResult {
+    pub symbol: String,
+    #[serde(rename = "baseCoin")]
+    pub base_coin: String,
+    #[serde(rename = "quoteCoin")]
+    pub quote_coin: String,
+    /// `Trading` when it can be traded
+    pub status: String,
+    /// Only for linear, `LinearPerpetual` or `LinearFutures`
+    #[serde(rename = "contractType")]
+    pub contract_type: Option<String>,
+    /// Only for linear, in milliseconds and "0" for perpetuals
+    #[serde(rename = "deliveryTime")]
+    pub delivery_time: Option<String>,
+    /// Only for linear, in minutes
+    #[serde(rename = "fundingInterval")]
+    pub funding_interval: Option<i64>
+}
+
+/// Reference: https://bybit-exchange.github.io/docs/v5/market/orderbook
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderBookResult {
+    pub s: String, // Symbol
+    pub b: Vec<Vec<String>>, // Bids
+    pub a: Vec<Vec<String>>, // Asks
+    pub ts: i64 // Timestamp
+}
+
+/// Reference: https://bybit-exchange.github.io/docs/v5/asset/coin-info
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CoinInfoResult {
+    pub rows: Vec<CoinInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CoinInfo {
+    pub name: String,
+    pub coin: String,
+    pub chains: Vec<ChainInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ChainInfo {
+    pub chain: String,
+    #[serde(rename = "chainType")]
+    pub chain_type: String,
+    #[serde(rename = "withdrawFee")]
+    pub withdraw_fee: String,
+    #[serde(rename = "withdrawMin")]
+    pub withdraw_min: String,
+    /// "1" means deposit is enabled
+    #[serde(rename = "chainDeposit")]
+    pub chain_deposit: String,
+    /// "1" means withdrawal is enabled
+    #[serde(rename = "chainWithdraw")]
+    pub chain_withdraw: String,
+    pub confirmation: String
+}
+
+impl ChainInfo {
+    /// Matches the network name or exactly the network id like "ARBI"
+    pub fn is_network(&self, withdrawal_chain: &str) -> bool {
+        self.chain_type.to_ascii_uppercase().contains(&withdrawal_chain.to_ascii_uppercase())
+            || self.chain.eq_ignore_ascii_case(withdrawal_chain)
+    }
+}
+
+/// Reference: https://bybit-exchange.github.io/docs/v5/account/wallet-balance
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WalletBalance {
+    #[serde(rename = "accountType")]
+    pub account_type: String,
+    #[serde(rename = "totalEquity")]
+    pub total_equity: String,
+    pub coin: Vec<CoinBalance>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CoinBalance {
+    pub coin: String,
+    #[serde(rename = "walletBalance")]
+    pub wallet_balance: String,
+    pub locked: String
+}
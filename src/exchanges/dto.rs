@@ -1,6 +1,224 @@
-#[derive(Debug, Clone)]
+use rust_decimal::Decimal;
+
+use crate::exchanges::symbol::MarketType;
+use crate::utils::error::HttpError;
+use crate::utils::number_utils::parse_decimal;
+
+#[derive(Debug, Clone, Default)]
 pub struct PriceResult {
     pub data_source: String,
     pub instrument: String,
-    pub price: f32
-}
\ No newline at end of file
+    pub market_type: MarketType,
+    /// Quote currency of the native instrument, the prices are in this currency
+    pub quote_ccy: String,
+    pub price: Decimal,
+    pub best_bid: Option<OrderBookLevel>,
+    pub best_ask: Option<OrderBookLevel>,
+    /// Effective price of buying `Instruments.notional_size` from the asks, without trading fee
+    pub buy_price: Option<Decimal>,
+    /// Effective price of selling `Instruments.notional_size` to the bids, without trading fee
+    pub sell_price: Option<Decimal>,
+    /// `Exchanges.trading_fee_rate` in percent, applied on each side when comparing venues
+    pub trading_fee_rate: Decimal,
+    /// Prices before the conversion to a common quote currency, `None` if they were already in it
+    pub normalization: Option<QuoteNormalization>
+}
+
+/// Raw prices of a `PriceResult` quoted in another currency than the compared one, e.g. a USD pair against USDC pairs
+#[derive(Debug, Clone, Default)]
+pub struct QuoteNormalization {
+    pub raw_quote_ccy: String,
+    /// Price of one raw_quote_ccy in the common quote currency
+    pub rate: Decimal,
+    pub raw_price: Decimal,
+    pub raw_buy_price: Option<Decimal>,
+    pub raw_sell_price: Option<Decimal>
+}
+
+impl PriceResult {
+    /// The native symbol, followed by the market type if it's a derivative
+    pub fn display_instrument(&self) -> String {
+        match self.market_type {
+            MarketType::Spot => self.instrument.clone(),
+            market_type => format!("{} {}", self.instrument, market_type.as_str())
+        }
+    }
+
+    /// Middle of the best bid and ask, without trading fee
+    pub fn mid_price(&self) -> Option<Decimal> {
+        match (&self.best_bid, &self.best_ask) {
+            (Some(bid), Some(ask)) => Some((bid.price + ask.price) / Decimal::TWO),
+            _ => None
+        }
+    }
+
+    /// Convert every price to `quote_ccy` with `rate`, the price of one current quote currency in `quote_ccy`
+    pub fn normalize(self, quote_ccy: &str, rate: Decimal) -> PriceResult {
+        let convert = |level: Option<OrderBookLevel>| level.map(|level| OrderBookLevel { price: level.price * rate, ..level });
+        PriceResult {
+            quote_ccy: quote_ccy.to_string(),
+            price: self.price * rate,
+            best_bid: convert(self.best_bid),
+            best_ask: convert(self.best_ask),
+            buy_price: self.buy_price.map(|price| price * rate),
+            sell_price: self.sell_price.map(|price| price * rate),
+            normalization: Some(QuoteNormalization {
+                raw_quote_ccy: self.quote_ccy,
+                rate,
+                raw_price: self.price,
+                raw_buy_price: self.buy_price,
+                raw_sell_price: self.sell_price
+            }),
+            ..self
+        }
+    }
+}
+
+const HOURS_PER_YEAR: i64 = 24 * 365;
+pub const MILLIS_PER_HOUR: i64 = 3_600_000;
+/// Funding interval of most perpetuals, used when the exchange doesn't tell
+pub const DEFAULT_FUNDING_HOURS: i64 = 8;
+
+/// Funding of one perpetual swap, the rates are fractions like `0.0001` for 0.01%
+#[derive(Debug, Clone, Default)]
+pub struct FundingInfo {
+    pub data_source: String,
+    pub instrument: String,
+    /// Rate of the current period, longs pay shorts when it's positive
+    pub funding_rate: Decimal,
+    /// Rate of the next period, only if the exchange publishes it
+    pub predicted_funding_rate: Option<Decimal>,
+    /// Hours between two fundings
+    pub interval_hours: Decimal,
+    /// In milliseconds
+    pub next_funding_time: Option<i64>,
+    pub mark_price: Option<Decimal>,
+    pub index_price: Option<Decimal>
+}
+
+impl FundingInfo {
+    /// Current funding rate in percent per year
+    pub fn annualized_percent(&self) -> Decimal {
+        (self.funding_rate * Decimal::from(HOURS_PER_YEAR) * Decimal::ONE_HUNDRED).checked_div(self.interval_hours).unwrap_or_default()
+    }
+
+    /// Mark price above the index price in percent, the index price stands for the spot price
+    pub fn basis_percent(&self) -> Option<Decimal> {
+        let (mark_price, index_price) = (self.mark_price?, self.index_price?);
+        ((mark_price - index_price) * Decimal::ONE_HUNDRED).checked_div(index_price)
+    }
+}
+
+/// Withdrawal and deposit status of `Instruments.withdrawal_chain` on one exchange
+#[derive(Debug, Clone)]
+pub struct NetworkInfo {
+    pub data_source: String,
+    pub chain: String,
+    pub withdraw_enable: bool,
+    pub deposit_enable: bool,
+    /// In target_ccy
+    pub withdraw_fee: Decimal
+}
+
+/// Buy on one venue and sell on another one
+#[derive(Debug, Clone)]
+pub struct ArbitrageOpportunity {
+    pub buy: PriceResult,
+    pub sell: PriceResult,
+    pub buy_price: Decimal,
+    pub sell_price: Decimal,
+    /// Sell price minus buy price
+    pub gross_spread: Decimal,
+    pub gross_percent: Decimal,
+    /// Fee of withdrawing from the buy venue in target_ccy, `None` if the network info is unknown
+    pub withdrawal_fee: Option<Decimal>,
+    /// Gross spread minus trading fees of both sides and the withdrawal fee
+    pub net_spread: Decimal,
+    pub net_percent: Decimal,
+    /// Why the coins can't be moved from the buy venue to the sell venue
+    pub non_actionable_reason: Option<String>
+}
+
+impl ArbitrageOpportunity {
+    pub fn is_actionable(&self) -> bool {
+        self.non_actionable_reason.is_none()
+    }
+}
+
+/// Balance of one asset in the exchange account
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetBalance {
+    pub data_source: String,
+    pub asset: String,
+    /// Available to trade or withdraw
+    pub free: Decimal,
+    /// Held by open orders and pending withdrawals
+    pub locked: Decimal
+}
+
+impl AssetBalance {
+    /// Build from the amounts returned by exchanges, an empty or invalid amount is an error instead of zero
+    /// so it never looks like a low balance
+    pub fn from_raw(data_source: &str, asset: &str, free: &str, locked: &str) -> Result<Self, HttpError> {
+        let parse_amount = |amount: &str| parse_decimal(amount).ok_or_else(|| {
+            eprintln!("[{data_source}] Failed to parse {asset} balance {amount:?} to number");
+            HttpError::ResponseDataError
+        });
+
+        Ok(Self {
+            data_source: data_source.to_string(),
+            asset: asset.to_ascii_uppercase(),
+            free: parse_amount(free)?,
+            locked: parse_amount(locked)?
+        })
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.free + self.locked
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderBookLevel {
+    pub price: Decimal,
+    pub size: Decimal
+}
+
+/// Bids are sorted from the highest price and asks from the lowest, like every exchange returns them
+#[derive(Debug, Clone, Default)]
+pub struct OrderBook {
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>
+}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<OrderBookLevel> {
+        self.bids.first().cloned()
+    }
+
+    pub fn best_ask(&self) -> Option<OrderBookLevel> {
+        self.asks.first().cloned()
+    }
+
+    /// Build from the `[price, size, ...]` string arrays returned by exchanges,
+    /// `size_multiplier` converts contracts to coins for derivatives, use `Decimal::ONE` for spot
+    pub fn from_raw_levels(bids: Vec<Vec<String>>, asks: Vec<Vec<String>>, size_multiplier: Decimal) -> Result<Self, HttpError> {
+        Ok(Self {
+            bids: parse_levels(bids, size_multiplier)?,
+            asks: parse_levels(asks, size_multiplier)?
+        })
+    }
+}
+
+fn parse_levels(levels: Vec<Vec<String>>, size_multiplier: Decimal) -> Result<Vec<OrderBookLevel>, HttpError> {
+    levels.iter()
+        .map(|level| {
+            let price = level.first().and_then(|item| parse_decimal(item));
+            let size = level.get(1).and_then(|item| parse_decimal(item));
+            match (price, size) {
+                (Some(price), Some(size)) => Ok(OrderBookLevel { price, size: size * size_multiplier }),
+                _ => Err(HttpError::ResponseDataError)
+            }
+        })
+        .collect()
+}
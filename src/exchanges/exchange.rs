@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+
+use crate::exchanges::dto::{AssetBalance, FundingInfo, NetworkInfo, PriceResult};
+use crate::exchanges::symbol::InstrumentInfo;
+use crate::utils::config_struct::{Exchanges, Instruments};
+use crate::utils::error::HttpError;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// Common behaviour of every exchange actor, so the monitors don't need to know which venue they are talking to.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait Exchange: Send + Sync {
+    /// Name of the exchange, it should match `Exchanges.name` in config.toml
+    fn data_source(&self) -> String;
+
+    /// Every tradable instrument of the exchange, used to resolve the native symbol of `Instruments`
+    async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError>;
+
+    /// Price of `symbol`, which is resolved from `instruments` by the instrument catalog of the exchange
+    async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError>;
+
+    /// Funding rate, mark and index price of a perpetual `symbol`
+    async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError>;
+
+    /// Withdrawal and deposit info of `Instruments.withdrawal_chain`
+    async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError>;
+
+    async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError>;
+
+    /// Free and locked amount of every asset in the account, it needs the api keys
+    async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError>;
+}
@@ -1,15 +1,19 @@
 use std::collections::HashMap;
 use std::env;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use crate::exchanges::dto::PriceResult;
+use crate::exchanges::dto::{AssetBalance, FundingInfo, NetworkInfo, OrderBook, PriceResult};
+use crate::exchanges::exchange::Exchange;
+use crate::exchanges::symbol::{InstrumentInfo, MarketType};
 use crate::exchanges::hashkey::{
     connector::HashKeyConnector,
-    dto::SymbolPriceTicker
+    dto::{AccountInfo, Depth, ExchangeInfo, SymbolPriceTicker}
 };
 use crate::utils::config_struct::{Exchanges, Instruments};
 use crate::utils::error::HttpError;
-use crate::utils::number_utils::calculate_price_with_trading_fee;
+use crate::utils::number_utils::{calculate_effective_prices, calculate_price_with_trading_fee};
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
@@ -34,33 +38,72 @@ impl HashKeyActor {
         Self { api_key, secret_key, data_source }
     }
 
-    pub async fn fetch_price(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+    pub async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
         let data_source = "HashKey".to_string();
         let target_ccy = instruments.target_ccy.to_ascii_uppercase();
-        let base_ccy = instruments.base_ccy
-            .replace("USDT", "USD")
-            .replace("USDC", "USD")
-            .to_ascii_uppercase();
-        let inst_id = format!("{target_ccy}{base_ccy}");
+        let inst_id = symbol.symbol;
+        // Only the spot api is integrated
+        if symbol.market_type != MarketType::Spot {
+            return Err(HttpError::Unsupported)
+        }
 
         let uri = "/quote/v1/ticker/price".to_string();
         let parameters = format!("symbol={inst_id}");
         let hashkey = HashKeyConnector::new(self.api_key.clone(), self.secret_key.clone());
         let data_vec = hashkey.http_client::<Vec<SymbolPriceTicker>>(exchange_config.clone().url, uri.clone(), parameters).await?;
-        let data = data_vec.get(0).unwrap();
+        let data = data_vec.first().ok_or(HttpError::ResponseDataError)?;
         let original_price = data.clone().p;
 
         let price = calculate_price_with_trading_fee(
             data_source.clone(),
             original_price.clone(),
             exchange_config.clone().trading_fee_rate
+        )?;
+
+        let order_book = self.fetch_order_book(inst_id.clone(), exchange_config.clone()).await?;
+        let (buy_price, sell_price) = calculate_effective_prices(
+            data_source.clone(), &order_book, instruments.notional_size
         );
 
-        println!("[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}]");
-        Ok(PriceResult { data_source, instrument: inst_id, price })
+        println!("[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}] [Buy: {:?}] [Sell: {:?}]", buy_price, sell_price);
+        Ok(PriceResult {
+            data_source,
+            instrument: inst_id,
+            market_type: symbol.market_type,
+            quote_ccy: symbol.base_ccy,
+            price,
+            best_bid: order_book.best_bid(),
+            best_ask: order_book.best_ask(),
+            buy_price,
+            sell_price,
+            trading_fee_rate: exchange_config.trading_fee_rate,
+            normalization: None
+        })
+    }
+
+    pub async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        let uri = "/api/v1/exchangeInfo".to_string();
+
+        let hashkey = HashKeyConnector::new(self.api_key.clone(), self.secret_key.clone());
+        let data = hashkey.http_client::<ExchangeInfo>(exchange_config.url, uri, "".to_string()).await?;
+
+        Ok(data.symbols.iter()
+            .filter(|item| item.status == "TRADING")
+            .map(|item| InstrumentInfo::new(&item.symbol, &item.base_asset, &item.quote_asset, MarketType::Spot))
+            .collect())
     }
 
-    pub async fn fetch_account(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<(), HttpError> {
+    pub async fn fetch_order_book(&self, inst_id: String, exchange_config: Exchanges) -> Result<OrderBook, HttpError> {
+        let uri = "/quote/v1/depth".to_string();
+        let parameters = format!("symbol={inst_id}&limit=200");
+
+        let hashkey = HashKeyConnector::new(self.api_key.clone(), self.secret_key.clone());
+        let data = hashkey.http_client::<Depth>(exchange_config.url, uri, parameters).await?;
+
+        OrderBook::from_raw_levels(data.b, data.a, Decimal::ONE)
+    }
+
+    pub async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
         let uri = "/api/v1/account/checkApiKey".to_string();
         let hashkey = HashKeyConnector::new(self.api_key.clone(), self.secret_key.clone());
         let data = hashkey.http_client::<HashMap<String, String>>(exchange_config.clone().url, uri.clone(), "".to_string()).await?;
@@ -68,4 +111,46 @@ impl HashKeyActor {
 
         Ok(())
     }
+
+    /// Balances of the main trading account
+    pub async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        let uri = "/api/v1/account".to_string();
+        let hashkey = HashKeyConnector::new(self.api_key.clone(), self.secret_key.clone());
+        let data = hashkey.http_client::<AccountInfo>(exchange_config.url.clone(), uri, "".to_string()).await?;
+
+        data.balances.iter()
+            .map(|balance| AssetBalance::from_raw(&self.data_source, &balance.asset, &balance.free, &balance.locked))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Exchange for HashKeyActor {
+    fn data_source(&self) -> String {
+        self.data_source.clone()
+    }
+
+    async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        HashKeyActor::fetch_instruments(self, exchange_config).await
+    }
+
+    async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        HashKeyActor::fetch_price(self, instruments, symbol, exchange_config).await
+    }
+
+    async fn fetch_funding(&self, _symbol: InstrumentInfo, _exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        Err(HttpError::Unsupported)
+    }
+
+    async fn fetch_ccy_info(&self, _instruments: Instruments, _exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        Err(HttpError::Unsupported)
+    }
+
+    async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        HashKeyActor::fetch_account(self, exchange_config).await
+    }
+
+    async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        HashKeyActor::fetch_balances(self, exchange_config).await
+    }
 }
@@ -44,7 +44,7 @@ impl HashKeyConnector {
         };
 
         let raw_sign = sign(total_parameters.clone(), self.secret_key.clone())
-            .expect(&*format!("Failed to create {} signature", self.data_source.clone()));
+            .unwrap_or_else(|_| panic!("Failed to create {} signature", self.data_source));
 
         let encoded_sign = hex_encode(raw_sign);
 
@@ -71,11 +71,11 @@ impl HashKeyConnector {
         let uri_with_sign = signature.full_uri;
         let response = client.send_request(url, uri_with_sign, headers).await?;
 
-        return if response.status().is_success() {
+        if response.status().is_success() {
             let parsed_response = response
                 .json::<T>()
                 .await
-                .expect(&*format!("[{}] Failed to deserialize response", data_source.clone()));
+                .unwrap_or_else(|_| panic!("[{}] Failed to deserialize response", data_source));
             Ok(parsed_response.clone())
         } else {
             eprintln!("[{data_source}] {:?}", response);
@@ -5,3 +5,41 @@ pub struct SymbolPriceTicker {
     pub s: String, // Symbol
     pub p: String, // Price
 }
+
+/// Reference: https://hashkeypro-apidoc.readme.io/reference/get-order-book
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Depth {
+    pub t: i64, // Timestamp
+    pub b: Vec<Vec<String>>, // Bids
+    pub a: Vec<Vec<String>>, // Asks
+}
+
+/// Reference: https://hashkeypro-apidoc.readme.io/reference/get-account-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountInfo {
+    pub balances: Vec<Balance>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Balance {
+    pub asset: String,
+    pub free: String,
+    pub locked: String
+}
+
+/// Reference: https://hashkeypro-apidoc.readme.io/reference/exchangeinfo
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExchangeInfo {
+    pub symbols: Vec<SymbolInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    /// `TRADING` when it can be traded
+    pub status: String,
+    #[serde(rename = "baseAsset")]
+    pub base_asset: String,
+    #[serde(rename = "quoteAsset")]
+    pub quote_asset: String
+}
@@ -1,3 +1,3 @@
 pub mod connector;
 pub mod actor;
-mod dto;
\ No newline at end of file
+pub mod dto;
\ No newline at end of file
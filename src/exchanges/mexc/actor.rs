@@ -1,18 +1,25 @@
 use std::env;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 
-use crate::exchanges::dto::PriceResult;
+use crate::exchanges::dto::{AssetBalance, FundingInfo, NetworkInfo, OrderBook, PriceResult};
+use crate::exchanges::exchange::Exchange;
+use crate::exchanges::symbol::{InstrumentInfo, MarketType};
 use crate::exchanges::mexc::{
     connector::MexcConnector,
     dto::SymbolPriceTicker
 };
 use crate::utils::config_struct::{Exchanges, Instruments};
 use crate::utils::error::HttpError;
-use crate::utils::number_utils::calculate_price_with_trading_fee;
+use crate::utils::number_utils::{calculate_effective_prices, calculate_price_with_trading_fee, parse_decimal};
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
-use crate::exchanges::mexc::dto::AllCcyInfo;
+use crate::exchanges::mexc::dto::{
+    levels_to_strings, number_to_string, AccountInfo, AllCcyInfo, ContractDepth, ContractDetail, ContractFundingRate, ContractResponse,
+    ContractTicker, Depth, ExchangeInfo
+};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MexcActor {
@@ -34,29 +41,147 @@ impl MexcActor {
         Self { api_key, secret_key, data_source }
     }
 
-    pub async fn fetch_price(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+    pub async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
         let data_source = self.data_source.clone();
         let target_ccy = instruments.target_ccy.to_ascii_uppercase();
-        let base_ccy = instruments.base_ccy.to_ascii_uppercase();
-        let inst_id = format!("{target_ccy}{base_ccy}");
-
-        let uri = "/api/v3/ticker/price".to_string();
-        let parameters = format!("symbol={inst_id}");
+        let inst_id = symbol.symbol.clone();
 
-        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
-        let data = mexc.http_client::<SymbolPriceTicker>(exchange_config.clone().url, uri.clone(), parameters, false).await?;
-        let original_price = data.clone().price;
+        let original_price = self.fetch_last_price(symbol.clone(), exchange_config.clone()).await?;
         let price = calculate_price_with_trading_fee(
             data_source.clone(),
-            data.clone().price,
+            original_price.clone(),
             exchange_config.clone().trading_fee_rate
+        )?;
+
+        let order_book = self.fetch_order_book(symbol.clone(), exchange_config.clone()).await?;
+        let (buy_price, sell_price) = calculate_effective_prices(
+            data_source.clone(), &order_book, instruments.notional_size
         );
 
-        println!("[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}]");
-        Ok(PriceResult { data_source, instrument: inst_id, price })
+        println!("[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}] [Buy: {:?}] [Sell: {:?}]", buy_price, sell_price);
+        Ok(PriceResult {
+            data_source,
+            instrument: inst_id,
+            market_type: symbol.market_type,
+            quote_ccy: symbol.base_ccy,
+            price,
+            best_bid: order_book.best_bid(),
+            best_ask: order_book.best_ask(),
+            buy_price,
+            sell_price,
+            trading_fee_rate: exchange_config.trading_fee_rate,
+            normalization: None
+        })
+    }
+
+    /// Spot pairs, and perpetuals of the contract api if `derivatives_url` is configured
+    pub async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        let data_source = self.data_source.clone();
+        let uri = "/api/v3/exchangeInfo".to_string();
+
+        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
+        let data = mexc.http_client::<ExchangeInfo>(exchange_config.url.clone(), uri, "".to_string(), false).await?;
+
+        let mut instruments: Vec<InstrumentInfo> = data.symbols.iter()
+            .filter(|item| item.is_spot_trading_allowed)
+            .map(|item| InstrumentInfo::new(&item.symbol, &item.base_asset, &item.quote_asset, MarketType::Spot))
+            .collect();
+
+        if let Some(derivatives_url) = exchange_config.derivatives_url {
+            let uri = "/api/v1/contract/detail".to_string();
+            let contracts = mexc.http_client::<ContractResponse<Vec<ContractDetail>>>(derivatives_url, uri, "".to_string(), false).await
+                .and_then(|response| response.into_data(&data_source));
+            match contracts {
+                Ok(contracts) => instruments.extend(contracts.iter()
+                    .filter(|item| item.state == 0)
+                    .map(|item| InstrumentInfo {
+                        contract_value: parse_decimal(&number_to_string(&item.contract_size)),
+                        ..InstrumentInfo::new(&item.symbol, &item.base_coin, &item.quote_coin, MarketType::Perp)
+                    })
+                ),
+                Err(e) => eprintln!("[{data_source}] Failed to fetch the contract instruments: {e}")
+            }
+        }
+
+        Ok(instruments)
+    }
+
+    pub async fn fetch_last_price(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<String, HttpError> {
+        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
+
+        match symbol.market_type {
+            MarketType::Spot => {
+                let uri = "/api/v3/ticker/price".to_string();
+                let parameters = format!("symbol={}", symbol.symbol);
+                let data = mexc.http_client::<SymbolPriceTicker>(exchange_config.url, uri, parameters, false).await?;
+                Ok(data.price)
+            }
+            MarketType::Perp => {
+                let url = exchange_config.derivatives_url.ok_or(HttpError::Unsupported)?;
+                let uri = "/api/v1/contract/ticker".to_string();
+                let parameters = format!("symbol={}", symbol.symbol);
+                let ticker = mexc.http_client::<ContractResponse<ContractTicker>>(url, uri, parameters, false).await?
+                    .into_data(&self.data_source)?;
+                Ok(number_to_string(&ticker.last_price))
+            }
+            MarketType::Futures => Err(HttpError::Unsupported)
+        }
+    }
+
+    pub async fn fetch_order_book(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<OrderBook, HttpError> {
+        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
+
+        match symbol.market_type {
+            MarketType::Spot => {
+                let uri = "/api/v3/depth".to_string();
+                let parameters = format!("symbol={}&limit=1000", symbol.symbol);
+                let data = mexc.http_client::<Depth>(exchange_config.url, uri, parameters, false).await?;
+
+                OrderBook::from_raw_levels(data.bids, data.asks, Decimal::ONE)
+            }
+            MarketType::Perp => {
+                let url = exchange_config.derivatives_url.ok_or(HttpError::Unsupported)?;
+                let uri = format!("/api/v1/contract/depth/{}", symbol.symbol);
+                let depth = mexc.http_client::<ContractResponse<ContractDepth>>(url, uri, "".to_string(), false).await?
+                    .into_data(&self.data_source)?;
+
+                // Volumes of contracts are number of contracts
+                let size_multiplier = symbol.contract_value.unwrap_or(Decimal::ONE);
+                OrderBook::from_raw_levels(
+                    levels_to_strings(depth.bids), levels_to_strings(depth.asks), size_multiplier
+                )
+            }
+            MarketType::Futures => Err(HttpError::Unsupported)
+        }
+    }
+
+    pub async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        let data_source = self.data_source.clone();
+        let url = exchange_config.derivatives_url.ok_or(HttpError::Unsupported)?;
+        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
+
+        let uri = format!("/api/v1/contract/funding_rate/{}", symbol.symbol);
+        let funding = mexc.http_client::<ContractResponse<ContractFundingRate>>(url.clone(), uri, "".to_string(), false).await?
+            .into_data(&data_source)?;
+
+        let uri = "/api/v1/contract/ticker".to_string();
+        let parameters = format!("symbol={}", symbol.symbol);
+        let ticker = mexc.http_client::<ContractResponse<ContractTicker>>(url, uri, parameters, false).await?
+            .into_data(&data_source)?;
+
+        Ok(FundingInfo {
+            data_source,
+            instrument: symbol.symbol,
+            funding_rate: parse_decimal(&number_to_string(&funding.funding_rate)).ok_or(HttpError::ResponseDataError)?,
+            predicted_funding_rate: None,
+            interval_hours: Decimal::from(funding.collect_cycle),
+            next_funding_time: Some(funding.next_settle_time),
+            mark_price: parse_decimal(&number_to_string(&ticker.fair_price)),
+            index_price: parse_decimal(&number_to_string(&ticker.index_price))
+        })
     }
 
-    pub async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<(), HttpError> {
+    pub async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
         let data_source = self.data_source.clone();
         let target_ccy = instruments.target_ccy.to_ascii_uppercase();
         let uri = "/api/v3/capital/config/getall".to_string();
@@ -64,13 +189,73 @@ impl MexcActor {
         let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
         let data = mexc.http_client::<Vec<AllCcyInfo>>(exchange_config.url.clone(), uri, "".to_string(), true).await?;
 
-        let coin_config_list = data.iter().find(|item| item.coin == target_ccy).unwrap();
+        let coin_config_list = data.iter().find(|item| item.coin == target_ccy).ok_or(HttpError::ResponseDataError)?;
         let coin_config = coin_config_list.network_list.iter().find(
             |item| item.network.to_ascii_uppercase().contains(instruments.withdrawal_chain.to_ascii_uppercase().as_str())
-        );
+        ).ok_or(HttpError::ResponseDataError)?;
+
+        println!("[{data_source}] CCY Data: {:?}", coin_config);
+
+        Ok(NetworkInfo {
+            data_source,
+            chain: coin_config.network.clone(),
+            withdraw_enable: coin_config.withdraw_enable,
+            deposit_enable: coin_config.deposit_enable,
+            withdraw_fee: parse_decimal(&coin_config.withdraw_fee).ok_or(HttpError::ResponseDataError)?
+        })
+    }
+
+    pub async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        let data_source = self.data_source.clone();
+        let uri = "/api/v3/account".to_string();
 
-        println!("[{data_source}] CCY Data: {:?}", coin_config.clone());
+        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
+        let data = mexc.http_client::<AccountInfo>(exchange_config.url.clone(), uri, "".to_string(), true).await?;
+        println!("[{data_source}] Account can trade: {}", data.can_trade);
 
         Ok(())
     }
+
+    /// Balances of the spot account
+    pub async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        let uri = "/api/v3/account".to_string();
+
+        let mexc = MexcConnector::new(self.api_key.clone(), self.secret_key.clone());
+        let data = mexc.http_client::<AccountInfo>(exchange_config.url.clone(), uri, "".to_string(), true).await?;
+
+        data.balances.iter()
+            .map(|balance| AssetBalance::from_raw(&self.data_source, &balance.asset, &balance.free, &balance.locked))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Exchange for MexcActor {
+    fn data_source(&self) -> String {
+        self.data_source.clone()
+    }
+
+    async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        MexcActor::fetch_instruments(self, exchange_config).await
+    }
+
+    async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        MexcActor::fetch_price(self, instruments, symbol, exchange_config).await
+    }
+
+    async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        MexcActor::fetch_funding(self, symbol, exchange_config).await
+    }
+
+    async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        MexcActor::fetch_ccy_info(self, instruments, exchange_config).await
+    }
+
+    async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        MexcActor::fetch_account(self, exchange_config).await
+    }
+
+    async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        MexcActor::fetch_balances(self, exchange_config).await
+    }
 }
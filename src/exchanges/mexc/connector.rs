@@ -44,7 +44,7 @@ impl MexcConnector {
         };
 
         let raw_sign = sign(total_parameters.clone(), self.secret_key.clone())
-            .expect(&*format!("Failed to create {} signature", self.data_source.clone()));;
+            .unwrap_or_else(|_| panic!("Failed to create {} signature", self.data_source));
         let encoded_sign = hex_encode(raw_sign);
 
         let full_uri = uri.clone() + "?" + total_parameters.clone().as_str() + "&signature=" + encoded_sign.as_str();
@@ -79,16 +79,15 @@ impl MexcConnector {
 
         let response = client.send_request(url, final_uri, headers).await?;
 
-        return if response.status().is_success() {
-            let parsed_response = response
-                .json::<T>()
-                .await
-                .expect(&*format!("[{}] Failed to deserialize response", data_source.clone()));
-            Ok(parsed_response.clone())
+        if response.status().is_success() {
+            response.json::<T>().await.map_err(|e| {
+                eprintln!("[{data_source}] Failed to deserialize response: {e}");
+                HttpError::ResponseDataError
+            })
         } else {
             eprintln!("[{data_source}] Response Error {:?}", response);
             Err(HttpError::ResponseError)
-        };
+        }
     }
 }
 
@@ -1,4 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::utils::error::HttpError;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SymbolPriceTicker {
@@ -6,6 +9,13 @@ pub struct SymbolPriceTicker {
     pub price: String,
 }
 
+/// Reference: https://mexcdevelop.github.io/apidocs/spot_v3_en/#order-book
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Depth {
+    pub bids: Vec<Vec<String>>,
+    pub asks: Vec<Vec<String>>
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AllCcyInfo {
     pub coin: String,
@@ -17,6 +27,8 @@ pub struct AllCcyInfo {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct CcyInfo {
     pub network: String,
+    #[serde(rename = "depositEnable")]
+    pub deposit_enable: bool,
     #[serde(rename = "withdrawEnable")]
     pub withdraw_enable: bool,
     #[serde(rename = "withdrawFee")]
@@ -33,5 +45,118 @@ pub struct CcyInfo {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AccountInfo {
     #[serde(rename = "canTrade")]
-    pub can_trade: bool
+    pub can_trade: bool,
+    #[serde(default)]
+    pub balances: Vec<Balance>
+}
+
+/// Reference: https://mexcdevelop.github.io/apidocs/spot_v3_en/#account-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Balance {
+    pub asset: String,
+    pub free: String,
+    pub locked: String
+}
+
+/// Reference: https://mexcdevelop.github.io/apidocs/spot_v3_en/#exchange-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExchangeInfo {
+    pub symbols: Vec<SymbolInfo>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SymbolInfo {
+    pub symbol: String,
+    #[serde(rename = "baseAsset")]
+    pub base_asset: String,
+    #[serde(rename = "quoteAsset")]
+    pub quote_asset: String,
+    #[serde(rename = "isSpotTradingAllowed")]
+    pub is_spot_trading_allowed: bool
+}
+
+/// Numbers of the contract api are json numbers instead of strings
+/// Reference: https://mexcdevelop.github.io/apidocs/contract_v1_en/#access-to-handle
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContractResponse<T> {
+    pub success: bool,
+    #[serde(default)]
+    pub code: i32,
+    /// Missing in the error responses
+    pub data: Option<T>,
+    pub message: Option<String>
+}
+
+impl<T> ContractResponse<T> {
+    /// The data of a successful response, the error code is logged otherwise
+    pub fn into_data(self, data_source: &str) -> Result<T, HttpError> {
+        match self.data {
+            Some(data) if self.success => Ok(data),
+            _ => {
+                eprintln!("[{data_source}] Contract api error {}: {}", self.code, self.message.unwrap_or_default());
+                Err(HttpError::ResponseDataError)
+            }
+        }
+    }
+}
+
+/// Reference: https://mexcdevelop.github.io/apidocs/contract_v1_en/#get-the-contract-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContractDetail {
+    pub symbol: String,
+    #[serde(rename = "baseCoin")]
+    pub base_coin: String,
+    #[serde(rename = "quoteCoin")]
+    pub quote_coin: String,
+    /// Size of one contract in base_coin
+    #[serde(rename = "contractSize")]
+    pub contract_size: Value,
+    /// 0 means enabled
+    pub state: i32
+}
+
+/// Reference: https://mexcdevelop.github.io/apidocs/contract_v1_en/#get-contract-trend-data
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContractTicker {
+    pub symbol: String,
+    #[serde(rename = "lastPrice")]
+    pub last_price: Value,
+    /// Mark price
+    #[serde(rename = "fairPrice")]
+    pub fair_price: Value,
+    #[serde(rename = "indexPrice")]
+    pub index_price: Value
+}
+
+/// Reference: https://mexcdevelop.github.io/apidocs/contract_v1_en/#get-contract-funding-rate
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContractFundingRate {
+    pub symbol: String,
+    #[serde(rename = "fundingRate")]
+    pub funding_rate: Value,
+    /// Funding interval in hours
+    #[serde(rename = "collectCycle")]
+    pub collect_cycle: i64,
+    /// In milliseconds
+    #[serde(rename = "nextSettleTime")]
+    pub next_settle_time: i64
+}
+
+/// Each level is `[price, volume, order count]`
+/// Reference: https://mexcdevelop.github.io/apidocs/contract_v1_en/#get-the-contract-s-depth-information
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContractDepth {
+    pub asks: Vec<Vec<Value>>,
+    pub bids: Vec<Vec<Value>>
+}
+
+pub fn number_to_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        value => value.to_string()
+    }
+}
+
+pub fn levels_to_strings(levels: Vec<Vec<Value>>) -> Vec<Vec<String>> {
+    levels.iter().map(|level| level.iter().map(number_to_string).collect()).collect()
 }
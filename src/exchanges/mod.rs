@@ -2,4 +2,9 @@ mod signer;
 pub mod okx;
 pub mod hashkey;
 pub mod mexc;
+pub mod binance;
+pub mod bybit;
 pub mod dto;
+pub mod exchange;
+pub mod registry;
+pub mod symbol;
@@ -1,15 +1,20 @@
 use std::collections::HashMap;
 use std::env;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::exchanges::dto::PriceResult;
+use crate::exchanges::dto::{AssetBalance, FundingInfo, NetworkInfo, OrderBook, PriceResult, DEFAULT_FUNDING_HOURS, MILLIS_PER_HOUR};
+use crate::exchanges::exchange::Exchange;
 use crate::exchanges::okx::connector::OkxConnector;
+use crate::exchanges::symbol::{InstrumentInfo, MarketType};
 use crate::utils::config_struct::{Exchanges, Instruments};
 
 #[cfg(test)]
 use mockall::{automock, predicate::*};
-use crate::exchanges::okx::dto::CcyData;
-use crate::utils::number_utils::calculate_price_with_trading_fee;
+use crate::exchanges::okx::dto::{AccountBalanceData, BalanceDetail, CcyData, FundingRateData, IndexTickerData, InstrumentData, MarkPriceData, OrderBookData};
+use crate::utils::number_utils::{calculate_effective_prices, calculate_price_with_trading_fee, parse_decimal};
 use crate::utils::error::HttpError;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -36,30 +41,121 @@ impl OkxActor {
 
     }
 
-    pub async fn fetch_price(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+    pub async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
         let data_source = self.data_source.clone();
         let target_ccy = instruments.target_ccy.to_ascii_uppercase();
-        let base_ccy = instruments.base_ccy.to_ascii_uppercase();
-        let inst_id = format!("{target_ccy}-{base_ccy}-SWAP");
+        let inst_id = symbol.symbol.clone();
 
         let uri = format!("/api/v5/market/ticker?instId={inst_id}");
         let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
 
         let data_vec = okx.http_client::<HashMap<String, String>>(exchange_config.url.clone(), uri).await?;
-        let data = data_vec.get(0).unwrap();
+        let data = data_vec.first().ok_or(HttpError::ResponseDataError)?;
 
-        let original_price = data.get("last").unwrap().to_string();
+        let original_price = data.get("last").ok_or(HttpError::ResponseDataError)?.to_string();
         let price = calculate_price_with_trading_fee(
             data_source.clone(),
             original_price.clone(),
             exchange_config.clone().trading_fee_rate
+        )?;
+
+        let (market_type, quote_ccy) = (symbol.market_type, symbol.base_ccy.clone());
+        let order_book = self.fetch_order_book(symbol, exchange_config.clone()).await?;
+        let (buy_price, sell_price) = calculate_effective_prices(
+            data_source.clone(), &order_book, instruments.notional_size
         );
-        println!("[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}]");
+        println!("[{data_source}] {target_ccy} price: [Original: {original_price}] [With trading fee: {price}] [Buy: {:?}] [Sell: {:?}]", buy_price, sell_price);
+
+        Ok(PriceResult {
+            data_source: self.data_source.clone(),
+            instrument: inst_id,
+            market_type,
+            quote_ccy,
+            price,
+            best_bid: order_book.best_bid(),
+            best_ask: order_book.best_ask(),
+            buy_price,
+            sell_price,
+            trading_fee_rate: exchange_config.trading_fee_rate,
+            normalization: None
+        })
+    }
+
+    pub async fn fetch_order_book(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<OrderBook, HttpError> {
+        let uri = format!("/api/v5/market/books?instId={}&sz=400", symbol.symbol);
+        let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
+
+        let data_vec = okx.http_client::<OrderBookData>(exchange_config.url.clone(), uri).await?;
+        let data = data_vec.first().ok_or(HttpError::ResponseDataError)?.clone();
+
+        // Sizes of derivatives are number of contracts
+        let size_multiplier = symbol.contract_value.unwrap_or(Decimal::ONE);
+
+        OrderBook::from_raw_levels(data.bids, data.asks, size_multiplier)
+    }
+
+    /// Live spot pairs, linear perpetual swaps and linear futures, an instrument type that fails to load is skipped
+    pub async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
+        let mut instruments = Vec::new();
+        let mut last_error = None;
+
+        for inst_type in ["SPOT", "SWAP", "FUTURES"] {
+            let uri = format!("/api/v5/public/instruments?instType={inst_type}");
+            match okx.http_client::<InstrumentData>(exchange_config.url.clone(), uri).await {
+                Ok(data_vec) => instruments.extend(data_vec.iter().filter(|item| item.state == "live").filter_map(to_instrument_info)),
+                Err(e) => {
+                    eprintln!("[{}] Failed to fetch {inst_type} instruments, skipped: {e}", self.data_source);
+                    last_error = Some(e)
+                }
+            }
+        }
+
+        match last_error {
+            Some(e) if instruments.is_empty() => Err(e),
+            _ => Ok(instruments)
+        }
+    }
 
-        Ok(PriceResult { data_source: self.data_source.clone(), instrument: inst_id, price })
+    pub async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        let data_source = self.data_source.clone();
+        let inst_id = symbol.symbol.clone();
+        let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
+
+        let uri = format!("/api/v5/public/funding-rate?instId={inst_id}");
+        let funding_vec = okx.http_client::<FundingRateData>(exchange_config.url.clone(), uri).await?;
+        let funding = funding_vec.first().ok_or(HttpError::ResponseDataError)?;
+
+        let uri = format!("/api/v5/public/mark-price?instType=SWAP&instId={inst_id}");
+        let mark_price = okx.http_client::<MarkPriceData>(exchange_config.url.clone(), uri).await?
+            .first()
+            .and_then(|item| parse_decimal(&item.mark_px));
+
+        // The index of a swap is the underlying, e.g. `ETH-USDT`
+        let uri = format!("/api/v5/market/index-tickers?instId={}-{}", symbol.target_ccy, symbol.base_ccy);
+        let index_price = okx.http_client::<IndexTickerData>(exchange_config.url, uri).await?
+            .first()
+            .and_then(|item| parse_decimal(&item.idx_px));
+
+        let interval_hours = match (funding.funding_time.parse::<i64>(), funding.next_funding_time.parse::<i64>()) {
+            (Ok(funding_time), Ok(next_funding_time)) if next_funding_time > funding_time =>
+                Decimal::from(next_funding_time - funding_time) / Decimal::from(MILLIS_PER_HOUR),
+            _ => Decimal::from(DEFAULT_FUNDING_HOURS)
+        };
+
+        Ok(FundingInfo {
+            data_source,
+            instrument: inst_id,
+            funding_rate: parse_decimal(&funding.funding_rate).ok_or(HttpError::ResponseDataError)?,
+            predicted_funding_rate: parse_decimal(&funding.next_funding_rate),
+            interval_hours,
+            next_funding_time: funding.funding_time.parse::<i64>().ok(),
+            mark_price,
+            index_price
+        })
     }
 
-    pub async fn fetch_ccy_data(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<(), HttpError> {
+    pub async fn fetch_ccy_data(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
         let data_source = self.data_source.clone();
         let target_ccy = instruments.target_ccy.to_ascii_uppercase();
         let uri = format!("/api/v5/asset/currencies?ccy={target_ccy}");
@@ -67,14 +163,100 @@ impl OkxActor {
         let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
 
         let data = okx.http_client::<CcyData>(exchange_config.url.clone(), uri).await?;
-        let ccy_data = data.iter().find(
-            |item|
-                item.chain.to_ascii_uppercase().contains(instruments.withdrawal_chain.to_ascii_uppercase().as_str())
-                || item.chain.to_ascii_uppercase().contains(target_ccy.as_str())
-        );
+        let ccy_data = data.iter().find(|item| item.is_network(&instruments.withdrawal_chain))
+            .ok_or(HttpError::UnlistedInstrument(format!("{target_ccy} on {}", instruments.withdrawal_chain)))?;
 
         println!("[{data_source}] CCY Data: {:?}\n", ccy_data);
 
+        Ok(NetworkInfo {
+            data_source,
+            chain: ccy_data.chain.clone(),
+            withdraw_enable: ccy_data.can_wd,
+            deposit_enable: ccy_data.can_dep,
+            withdraw_fee: parse_decimal(&ccy_data.min_fee).ok_or(HttpError::ResponseDataError)?
+        })
+    }
+
+    pub async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        let data_source = self.data_source.clone();
+        let uri = "/api/v5/account/config".to_string();
+
+        let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
+        let data = okx.http_client::<HashMap<String, Value>>(exchange_config.url.clone(), uri).await?;
+        println!("[{data_source}] Account config: {:?}", data);
+
         Ok(())
     }
+
+    /// Balances of the trading account, assets in the funding account are not included
+    pub async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        let uri = "/api/v5/account/balance".to_string();
+
+        let okx = OkxConnector::new(self.api_key.clone(), self.secret_key.clone(), self.passphrase.clone());
+        let data_vec = okx.http_client::<AccountBalanceData>(exchange_config.url.clone(), uri).await?;
+        let data = data_vec.first().ok_or(HttpError::ResponseDataError)?;
+
+        data.details.iter()
+            .map(|detail| to_asset_balance(&self.data_source, detail))
+            .collect()
+    }
+}
+
+/// Without `availBal` in the account mode, the cash balance minus the frozen one is available
+pub fn to_asset_balance(data_source: &str, detail: &BalanceDetail) -> Result<AssetBalance, HttpError> {
+    if !detail.avail_bal.is_empty() {
+        return AssetBalance::from_raw(data_source, &detail.ccy, &detail.avail_bal, &detail.frozen_bal)
+    }
+    let balance = AssetBalance::from_raw(data_source, &detail.ccy, &detail.cash_bal, &detail.frozen_bal)?;
+    Ok(AssetBalance { free: balance.free - balance.locked, ..balance })
+}
+
+fn to_instrument_info(data: &InstrumentData) -> Option<InstrumentInfo> {
+    let market_type = match data.inst_type.as_str() {
+        "SPOT" => return Some(InstrumentInfo::new(&data.inst_id, &data.base_ccy, &data.quote_ccy, MarketType::Spot)),
+        "SWAP" => MarketType::Perp,
+        "FUTURES" => MarketType::Futures,
+        _ => return None
+    };
+    if data.ct_type != "linear" {
+        return None
+    }
+
+    let (target_ccy, base_ccy) = data.uly.split_once('-')?;
+    Some(InstrumentInfo {
+        contract_value: Some(parse_decimal(&data.ct_val)?),
+        expiry: data.exp_time.parse::<i64>().ok(),
+        ..InstrumentInfo::new(&data.inst_id, target_ccy, base_ccy, market_type)
+    })
+}
+
+#[async_trait]
+impl Exchange for OkxActor {
+    fn data_source(&self) -> String {
+        self.data_source.clone()
+    }
+
+    async fn fetch_instruments(&self, exchange_config: Exchanges) -> Result<Vec<InstrumentInfo>, HttpError> {
+        OkxActor::fetch_instruments(self, exchange_config).await
+    }
+
+    async fn fetch_price(&self, instruments: Instruments, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<PriceResult, HttpError> {
+        OkxActor::fetch_price(self, instruments, symbol, exchange_config).await
+    }
+
+    async fn fetch_funding(&self, symbol: InstrumentInfo, exchange_config: Exchanges) -> Result<FundingInfo, HttpError> {
+        OkxActor::fetch_funding(self, symbol, exchange_config).await
+    }
+
+    async fn fetch_ccy_info(&self, instruments: Instruments, exchange_config: Exchanges) -> Result<NetworkInfo, HttpError> {
+        self.fetch_ccy_data(instruments, exchange_config).await
+    }
+
+    async fn fetch_account(&self, exchange_config: Exchanges) -> Result<(), HttpError> {
+        OkxActor::fetch_account(self, exchange_config).await
+    }
+
+    async fn fetch_balances(&self, exchange_config: Exchanges) -> Result<Vec<AssetBalance>, HttpError> {
+        OkxActor::fetch_balances(self, exchange_config).await
+    }
 }
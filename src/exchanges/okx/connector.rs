@@ -74,17 +74,17 @@ impl OkxConnector {
             T: serde::de::DeserializeOwned + Clone {
         let data_source = self.data_source.clone();
         let timestamp = OffsetDateTime::now_utc();
-        let signature = self.sign("GET", &*uri.clone(), timestamp).unwrap();
+        let signature = self.sign("GET", &uri, timestamp).unwrap();
         let headers = self.build_headers(signature).unwrap();
 
         let client = HttpClient::new(data_source.clone());
         let response = client.send_request(url, uri, headers).await?;
 
-        return if response.status().is_success() {
+        if response.status().is_success() {
             let parsed_response = response
                 .json::<ApiResponse<T>>()
                 .await
-                .expect(&*format!("[{}] Failed to deserialize response", data_source.clone()));
+                .unwrap_or_else(|_| panic!("[{}] Failed to deserialize response", data_source));
 
             if parsed_response.code == "0" {
                 Ok(parsed_response.data)
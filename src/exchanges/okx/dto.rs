@@ -12,6 +12,8 @@ pub struct ApiResponse<T> {
 pub struct CcyData {
     #[serde(rename = "canWd")]
     pub can_wd: bool,
+    #[serde(rename = "canDep")]
+    pub can_dep: bool,
     pub ccy: String,
     pub chain: String,
     #[serde(rename = "minWd")]
@@ -25,3 +27,101 @@ pub struct CcyData {
     #[serde(rename = "mainNet")]
     pub mainnet: bool
 }
+
+impl CcyData {
+    /// `chain` is `{ccy}-{network}` like "ETH-Arbitrum One", only the network part is compared with the withdrawal chain
+    pub fn is_network(&self, withdrawal_chain: &str) -> bool {
+        let withdrawal_chain = withdrawal_chain.to_ascii_uppercase();
+        let network = self.chain.split_once('-').map_or(self.chain.as_str(), |(_, network)| network);
+        network.to_ascii_uppercase().contains(&withdrawal_chain) || self.chain.eq_ignore_ascii_case(&withdrawal_chain)
+    }
+}
+
+/// Reference: https://www.okx.com/docs-v5/en/#order-book-trading-market-data-get-order-book
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OrderBookData {
+    pub asks: Vec<Vec<String>>,
+    pub bids: Vec<Vec<String>>,
+    pub ts: String
+}
+
+/// Reference: https://www.okx.com/docs-v5/en/#public-data-rest-api-get-instruments
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstrumentData {
+    #[serde(rename = "instType")]
+    pub inst_type: String,
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    /// Only for spot
+    #[serde(rename = "baseCcy")]
+    pub base_ccy: String,
+    /// Only for spot
+    #[serde(rename = "quoteCcy")]
+    pub quote_ccy: String,
+    /// Underlying like `ETH-USDT`, only for derivatives
+    pub uly: String,
+    /// Contract value, only for derivatives
+    #[serde(rename = "ctVal")]
+    pub ct_val: String,
+    /// `linear` or `inverse`, only for derivatives
+    #[serde(rename = "ctType")]
+    pub ct_type: String,
+    /// Expiry in milliseconds, only for futures
+    #[serde(rename = "expTime")]
+    pub exp_time: String,
+    /// `live` when it can be traded
+    pub state: String
+}
+
+/// Reference: https://www.okx.com/docs-v5/en/#public-data-rest-api-get-funding-rate
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FundingRateData {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "fundingRate")]
+    pub funding_rate: String,
+    /// Empty when the predicted rate is not published
+    #[serde(rename = "nextFundingRate")]
+    pub next_funding_rate: String,
+    /// Settlement time of the current period in milliseconds
+    #[serde(rename = "fundingTime")]
+    pub funding_time: String,
+    #[serde(rename = "nextFundingTime")]
+    pub next_funding_time: String
+}
+
+/// Reference: https://www.okx.com/docs-v5/en/#public-data-rest-api-get-mark-price
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MarkPriceData {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "markPx")]
+    pub mark_px: String
+}
+
+/// Reference: https://www.okx.com/docs-v5/en/#trading-account-rest-api-get-balance
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AccountBalanceData {
+    pub details: Vec<BalanceDetail>
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BalanceDetail {
+    pub ccy: String,
+    /// Empty in the account modes without it
+    #[serde(rename = "availBal")]
+    pub avail_bal: String,
+    #[serde(rename = "cashBal")]
+    pub cash_bal: String,
+    #[serde(rename = "frozenBal")]
+    pub frozen_bal: String
+}
+
+/// Reference: https://www.okx.com/docs-v5/en/#public-data-rest-api-get-index-tickers
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IndexTickerData {
+    #[serde(rename = "instId")]
+    pub inst_id: String,
+    #[serde(rename = "idxPx")]
+    pub idx_px: String
+}
@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use futures::future::join_all;
+use rust_decimal::Decimal;
+
+use crate::exchanges::{
+    binance::actor::BinanceActor,
+    bybit::actor::BybitActor,
+    dto::{AssetBalance, FundingInfo, NetworkInfo, PriceResult},
+    exchange::Exchange,
+    hashkey::actor::HashKeyActor,
+    mexc::actor::MexcActor,
+    okx::actor::OkxActor,
+    symbol::{InstrumentCatalog, InstrumentInfo, MarketType}
+};
+use crate::utils::config_struct::{Exchanges, FundingInstrument, Instruments};
+use crate::utils::error::HttpError;
+use crate::utils::number_utils::{median, USD};
+
+/// Instrument lists older than this are downloaded again, to pick up new listings
+const CATALOG_TTL: Duration = Duration::from_secs(3600);
+
+pub struct RegisteredExchange {
+    pub config: Exchanges,
+    pub exchange: Box<dyn Exchange>,
+    /// `None` until `ExchangeRegistry::load_catalogs` succeeds for this exchange, with the time it was loaded
+    catalog: RwLock<Option<(Instant, InstrumentCatalog)>>
+}
+
+impl RegisteredExchange {
+    /// See `InstrumentCatalog::resolve`
+    pub fn resolve(&self, target_ccy: &str, base_ccy: &str, market_type: MarketType, symbol_override: Option<&String>) -> Result<InstrumentInfo, HttpError> {
+        match self.catalog.read().unwrap().as_ref() {
+            Some((_, catalog)) => catalog.resolve(target_ccy, base_ccy, market_type, symbol_override),
+            None => Err(HttpError::NoInstrumentCatalog)
+        }
+    }
+
+    fn is_catalog_stale(&self) -> bool {
+        self.catalog.read().unwrap().as_ref().is_none_or(|(loaded_at, _)| loaded_at.elapsed() >= CATALOG_TTL)
+    }
+}
+
+/// Holds the exchanges configured in config.toml, only those are instantiated and queried.
+#[derive(Default)]
+pub struct ExchangeRegistry {
+    pub exchanges: Vec<RegisteredExchange>
+}
+
+impl ExchangeRegistry {
+    pub fn from_config(exchange_configs: Vec<Exchanges>) -> Self {
+        let mut registry = Self::default();
+
+        for exchange_config in exchange_configs {
+            match build_exchange(exchange_config.name.as_str()) {
+                Some(exchange) => registry.register(exchange_config, exchange),
+                None => eprintln!("[Registry] Exchange {} is not supported, skipped", exchange_config.name)
+            }
+        }
+
+        registry
+    }
+
+    pub fn register(&mut self, config: Exchanges, exchange: Box<dyn Exchange>) {
+        self.exchanges.push(RegisteredExchange { config, exchange, catalog: RwLock::new(None) });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exchanges.is_empty()
+    }
+
+    /// Download the instrument list of all registered exchanges concurrently, it has to be done before fetching prices.
+    /// It's done once at startup and the registry is shared by the monitors.
+    pub async fn load_catalogs(&self) {
+        self.load_catalogs_of(self.exchanges.iter().collect()).await
+    }
+
+    /// Download the instrument lists that failed to load or are older than `CATALOG_TTL`,
+    /// called by the monitors before each run so a long running daemon follows the listings
+    pub async fn refresh_catalogs(&self) {
+        self.load_catalogs_of(self.exchanges.iter().filter(|item| item.is_catalog_stale()).collect()).await
+    }
+
+    async fn load_catalogs_of(&self, exchanges: Vec<&RegisteredExchange>) {
+        let futures = exchanges.iter().map(
+            |item| item.exchange.fetch_instruments(item.config.clone())
+        );
+        let results = join_all(futures).await;
+
+        for (item, result) in exchanges.into_iter().zip(results) {
+            match result {
+                Ok(instruments) => {
+                    println!("[{}] {} instruments listed", item.config.name, instruments.len());
+                    *item.catalog.write().unwrap() = Some((Instant::now(), InstrumentCatalog::new(instruments)));
+                }
+                // The previous list, if any, is kept until the next refresh
+                Err(e) => eprintln!("[{}] Failed to fetch the instrument list: {e}", item.config.name)
+            }
+        }
+    }
+
+    /// Fetch the price of one instrument from all registered exchanges concurrently,
+    /// exchanges that don't list the instrument return `HttpError::UnlistedInstrument` instead of a similar pair
+    pub async fn fetch_prices(&self, instruments: Instruments) -> Vec<Result<PriceResult, HttpError>> {
+        let futures = self.exchanges.iter().map(|item| {
+            let instruments = instruments.clone();
+            async move {
+                let symbol_override = instruments.symbol_override(&item.config.name);
+                let market_type = instruments.market_type(&item.config.name);
+                let symbol = item.resolve(&instruments.target_ccy, &instruments.base_ccy, market_type, symbol_override)?;
+                item.exchange.fetch_price(instruments, symbol, item.config.clone()).await
+            }
+        });
+
+        join_all(futures).await
+    }
+
+    /// Price of each currency in USD, the median of the mid prices of its USD pair on the registered exchanges.
+    /// Currencies that no exchange lists against USD are left out, the USDT or USDC pairs `resolve` falls back to
+    /// are not USD rates and are ignored.
+    pub async fn fetch_usd_rates(&self, currencies: &[String]) -> HashMap<String, Decimal> {
+        let mut usd_rates = HashMap::new();
+
+        for ccy in currencies.iter().filter(|ccy| !ccy.eq_ignore_ascii_case(USD)) {
+            let mid_prices = self.fetch_prices(Instruments::spot(ccy, USD, Decimal::ONE_THOUSAND)).await
+                .into_iter()
+                .filter_map(|result| result.ok().filter(|price_result| price_result.quote_ccy.eq_ignore_ascii_case(USD))?.mid_price())
+                .collect();
+            match median(mid_prices) {
+                Some(rate) => {
+                    usd_rates.insert(ccy.to_ascii_uppercase(), rate);
+                }
+                None => eprintln!("[Registry] No exchange quotes {ccy}/{USD}")
+            }
+        }
+
+        usd_rates
+    }
+
+    /// Fetch the funding of one perpetual from all registered exchanges concurrently
+    pub async fn fetch_fundings(&self, instrument: &FundingInstrument) -> Vec<Result<FundingInfo, HttpError>> {
+        let futures = self.exchanges.iter().map(|item| async move {
+            let symbol_override = instrument.symbol_override(&item.config.name);
+            let symbol = item.resolve(&instrument.target_ccy, &instrument.base_ccy, MarketType::Perp, symbol_override)?;
+            item.exchange.fetch_funding(symbol, item.config.clone()).await
+        });
+
+        join_all(futures).await
+    }
+
+    /// Fetch the currency and withdrawal chain info of one instrument from all registered exchanges concurrently
+    pub async fn fetch_ccy_infos(&self, instruments: Instruments) -> Vec<Result<NetworkInfo, HttpError>> {
+        let futures = self.exchanges.iter().map(
+            |item| item.exchange.fetch_ccy_info(instruments.clone(), item.config.clone())
+        );
+
+        join_all(futures).await
+    }
+
+    /// Check the account of all registered exchanges concurrently, mostly to verify the api keys
+    pub async fn fetch_accounts(&self) -> Vec<Result<(), HttpError>> {
+        let futures = self.exchanges.iter().map(
+            |item| item.exchange.fetch_account(item.config.clone())
+        );
+
+        join_all(futures).await
+    }
+
+    /// Fetch the account balances of the registered exchanges in `exchange_names` concurrently,
+    /// the others are not queried because their api keys may not be set
+    pub async fn fetch_balances(&self, exchange_names: &[String]) -> Vec<(&RegisteredExchange, Result<Vec<AssetBalance>, HttpError>)> {
+        let exchanges: Vec<&RegisteredExchange> = self.exchanges.iter()
+            .filter(|item| exchange_names.iter().any(|name| name.eq_ignore_ascii_case(&item.config.name)))
+            .collect();
+        let futures = exchanges.iter().map(
+            |item| item.exchange.fetch_balances(item.config.clone())
+        );
+        let results = join_all(futures).await;
+
+        exchanges.into_iter().zip(results).collect()
+    }
+}
+
+/// Add new exchanges here after implementing `Exchange` for its actor
+fn build_exchange(name: &str) -> Option<Box<dyn Exchange>> {
+    match name.to_ascii_uppercase().as_str() {
+        "OKX" => Some(Box::new(OkxActor::new())),
+        "HASHKEY" => Some(Box::new(HashKeyActor::new())),
+        "MEXC" => Some(Box::new(MexcActor::new())),
+        "BINANCE" => Some(Box::new(BinanceActor::new())),
+        "BYBIT" => Some(Box::new(BybitActor::new())),
+        _ => None
+    }
+}
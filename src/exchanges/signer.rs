@@ -15,9 +15,9 @@ pub fn sign(raw_sign: String, secret_key: String) -> Result<Hmac::<Sha256>, Sign
 }
 
 pub fn base64_encode(raw: Hmac::<Sha256>) -> String {
-    return general_purpose::STANDARD.encode(raw.finalize().into_bytes())
+    general_purpose::STANDARD.encode(raw.finalize().into_bytes())
 }
 
 pub fn hex_encode(raw: Hmac::<Sha256>) -> String {
-    return hex::encode(raw.finalize().into_bytes())
+    hex::encode(raw.finalize().into_bytes())
 }
@@ -0,0 +1,107 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::utils::error::HttpError;
+
+/// Quote currencies worth about one USD, a pair quoted in one of them can stand in for the others.
+/// The prices are converted back to the requested quote through the USD rates afterwards.
+pub const USD_QUOTES: [&str; 3] = ["USD", "USDT", "USDC"];
+
+/// Product type of an instrument, prices of different market types are never compared with each other
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarketType {
+    #[default]
+    Spot,
+    /// Linear perpetual swap, margined and settled in base_ccy
+    Perp,
+    /// Linear dated futures, the one with the nearest expiry is used
+    Futures
+}
+
+impl MarketType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            MarketType::Spot => "spot",
+            MarketType::Perp => "perp",
+            MarketType::Futures => "futures"
+        }
+    }
+}
+
+/// One tradable instrument listed by an exchange
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstrumentInfo {
+    /// Native symbol of the exchange, e.g. `ETH-USDC` on OKX or `ETHUSDC` on Binance
+    pub symbol: String,
+    pub target_ccy: String,
+    pub base_ccy: String,
+    pub market_type: MarketType,
+    /// Size of one contract in target_ccy, only for derivatives quoted in number of contracts
+    pub contract_value: Option<Decimal>,
+    /// Delivery time in milliseconds, only for futures
+    pub expiry: Option<i64>,
+    /// Hours between fundings, only for perpetuals of venues that publish it with the instrument list
+    pub funding_interval_hours: Option<i64>
+}
+
+impl InstrumentInfo {
+    pub fn new(symbol: &str, target_ccy: &str, base_ccy: &str, market_type: MarketType) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            target_ccy: target_ccy.to_ascii_uppercase(),
+            base_ccy: base_ccy.to_ascii_uppercase(),
+            market_type,
+            contract_value: None,
+            expiry: None,
+            funding_interval_hours: None
+        }
+    }
+}
+
+/// Instruments listed by one exchange, used to map `Instruments` to the native symbol of the exchange
+#[derive(Debug, Clone, Default)]
+pub struct InstrumentCatalog {
+    pub instruments: Vec<InstrumentInfo>
+}
+
+impl InstrumentCatalog {
+    pub fn new(instruments: Vec<InstrumentInfo>) -> Self {
+        Self { instruments }
+    }
+
+    /// Find the instrument of the pair and market type, `symbol_override` takes precedence over the listed pair.
+    /// If a USD-like pair isn't listed, the same pair in another of `USD_QUOTES` is used, and the returned
+    /// `base_ccy` is the quote it's actually listed in.
+    /// An override must be listed as well, otherwise its quote currency is unknown.
+    pub fn resolve(&self, target_ccy: &str, base_ccy: &str, market_type: MarketType, symbol_override: Option<&String>) -> Result<InstrumentInfo, HttpError> {
+        let target_ccy = target_ccy.to_ascii_uppercase();
+        let base_ccy = base_ccy.to_ascii_uppercase();
+
+        if let Some(symbol) = symbol_override {
+            return self.instruments.iter()
+                .find(|item| item.symbol.eq_ignore_ascii_case(symbol))
+                .cloned()
+                .ok_or(HttpError::UnlistedInstrument(symbol.to_string()))
+        }
+
+        let mut quotes = vec!(base_ccy.as_str());
+        if USD_QUOTES.contains(&base_ccy.as_str()) {
+            quotes.extend(USD_QUOTES.iter().filter(|quote| **quote != base_ccy && **quote != target_ccy));
+        }
+        quotes.into_iter()
+            .find_map(|quote| self.find(&target_ccy, quote, market_type))
+            .ok_or(HttpError::UnlistedInstrument(format!("{target_ccy}/{base_ccy} {}", market_type.as_str())))
+    }
+
+    /// The one with the nearest expiry for futures, the ones delivered since the catalog was loaded are skipped
+    fn find(&self, target_ccy: &str, base_ccy: &str, market_type: MarketType) -> Option<InstrumentInfo> {
+        let now = Utc::now().timestamp_millis();
+        self.instruments.iter()
+            .filter(|item| item.target_ccy == target_ccy && item.base_ccy == base_ccy && item.market_type == market_type)
+            .filter(|item| item.expiry.is_none_or(|expiry| expiry > now))
+            .min_by_key(|item| item.expiry)
+            .cloned()
+    }
+}
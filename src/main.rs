@@ -3,11 +3,23 @@ mod blockchain;
 mod exchanges;
 mod utils;
 mod monitors;
+mod notifiers;
+mod scheduler;
 mod unittest;
 
+use crate::blockchain::chain_registry::ChainRegistry;
+use crate::exchanges::registry::ExchangeRegistry;
+use crate::monitors::monitor_address::BalanceSources;
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::address_watch::AddressWatchStore;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::balance_history::BalanceHistoryStore;
+use crate::scheduler::daemon::{run_daemon, ScheduledJob};
 use crate::utils::config_struct::Config;
 
 use dotenv::dotenv;
+use futures::future::{FutureExt, LocalBoxFuture};
+use std::env;
 use std::time::Instant;
 use config_file::FromConfigFile;
 
@@ -18,13 +30,133 @@ async fn main() {
     dotenv().ok();
     let config: Config = Config::from_config_file("config.toml")
         .expect("Failed to read config file");
+    let notifiers = NotifierRegistry::from_config(config.notifiers.clone());
+    let alert_store = AlertStore::load(config.alert_state.clone());
+    let chains = ChainRegistry::from_config(&config.chains);
+    let watch_store = AddressWatchStore::load(config.address_watch.clone());
+    let history_store = BalanceHistoryStore::load(config.balance_history.clone());
+    let balance_sources = BalanceSources {
+        node_providers: &config.node_providers,
+        bitcoin: &config.bitcoin,
+        solana: &config.solana
+    };
+
+    if env::args().any(|arg| arg == "--check-accounts") {
+        monitors::monitor_cex::check_accounts(config.exchange_difference.exchanges.clone()).await;
+        return
+    }
+
+    // The instrument lists are shared by the price, funding and gas monitors, which refresh the stale ones
+    let exchanges = ExchangeRegistry::from_config(config.exchange_difference.exchanges.clone());
+    exchanges.load_catalogs().await;
+
+    if env::args().any(|arg| arg == "--daemon") {
+        let context = MonitorContext {
+            chains: &chains,
+            exchanges: &exchanges,
+            balance_sources: &balance_sources,
+            notifiers: &notifiers,
+            alert_store: &alert_store,
+            watch_store: &watch_store,
+            history_store: &history_store
+        };
+        run_daemon(build_scheduled_jobs(&config, &context)).await;
+        return
+    }
 
     tokio::join!(
-        monitors::monitor_address::addresses_balances(config.monitor_addresses),
-        monitors::monitor_cex::exchange_prices(config.exchange_difference),
-        monitors::monitor_ip::monitor_ip()
+        monitors::monitor_address::addresses_balances(
+            config.monitor_addresses.clone(),
+            &chains,
+            &balance_sources,
+            &notifiers,
+            &alert_store,
+            &watch_store,
+            &history_store
+        ),
+        monitors::monitor_cex::exchange_prices(config.exchange_difference.clone(), &exchanges, &notifiers, &alert_store),
+        monitors::monitor_exchange_balance::exchange_balances(config.monitor_exchange_balances.clone(), &exchanges, &notifiers, &alert_store),
+        monitors::monitor_funding::funding_rates(config.funding_rates.clone(), &exchanges, &notifiers, &alert_store),
+        monitors::monitor_gas::gas_prices(
+            config.monitor_gas_prices.clone(),
+            &exchanges,
+            &chains,
+            &config.node_providers,
+            &notifiers,
+            &alert_store
+        ),
+        monitors::monitor_ip::monitor_ip(),
+        monitors::monitor_node::nodes_health(
+            &config.monitor_addresses, &chains, &config.node_providers, &notifiers, &alert_store
+        )
     );
 
     let elapsed = now.elapsed();
     println!("Monitor jobs finished! Elapsed: {:.2?}", elapsed);
 }
+
+/// Shared by the scheduled jobs for the whole daemon run
+struct MonitorContext<'a> {
+    chains: &'a ChainRegistry,
+    exchanges: &'a ExchangeRegistry,
+    balance_sources: &'a BalanceSources<'a>,
+    notifiers: &'a NotifierRegistry,
+    alert_store: &'a AlertStore,
+    watch_store: &'a AddressWatchStore,
+    history_store: &'a BalanceHistoryStore
+}
+
+/// Only the monitors with a schedule in config.toml are run in daemon mode
+fn build_scheduled_jobs<'a>(config: &'a Config, context: &'a MonitorContext<'a>) -> Vec<ScheduledJob<'a>> {
+    let MonitorContext { chains, exchanges, balance_sources, notifiers, alert_store, watch_store, history_store } = *context;
+    let mut jobs: Vec<ScheduledJob<'a>> = vec!();
+    let mut add_job = |name: &str, job: Box<dyn Fn() -> LocalBoxFuture<'a, ()> + 'a>| {
+        match config.schedules.get(name) {
+            Some(schedule) => jobs.push(ScheduledJob { name: name.to_string(), schedule: schedule.clone(), job }),
+            None => println!("[Scheduler] {name} has no schedule, skipped")
+        }
+    };
+
+    add_job("addresses_balances", Box::new(
+        move || monitors::monitor_address::addresses_balances(
+            config.monitor_addresses.clone(),
+            chains,
+            balance_sources,
+            notifiers,
+            alert_store,
+            watch_store,
+            history_store
+        ).boxed_local()
+    ));
+    add_job("exchange_prices", Box::new(
+        move || monitors::monitor_cex::exchange_prices(config.exchange_difference.clone(), exchanges, notifiers, alert_store).boxed_local()
+    ));
+    add_job("exchange_balances", Box::new(
+        move || monitors::monitor_exchange_balance::exchange_balances(
+            config.monitor_exchange_balances.clone(), exchanges, notifiers, alert_store
+        ).boxed_local()
+    ));
+    add_job("funding_rates", Box::new(
+        move || monitors::monitor_funding::funding_rates(config.funding_rates.clone(), exchanges, notifiers, alert_store).boxed_local()
+    ));
+    add_job("gas_prices", Box::new(
+        move || monitors::monitor_gas::gas_prices(
+            config.monitor_gas_prices.clone(),
+            exchanges,
+            chains,
+            &config.node_providers,
+            notifiers,
+            alert_store
+        ).boxed_local()
+    ));
+    add_job("nodes_health", Box::new(
+        move || monitors::monitor_node::nodes_health(
+            &config.monitor_addresses, chains, &config.node_providers, notifiers, alert_store
+        ).boxed_local()
+    ));
+    add_job("monitor_ip", Box::new(
+        || monitors::monitor_ip::monitor_ip().boxed_local()
+    ));
+
+    jobs
+}
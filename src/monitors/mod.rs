@@ -1,3 +1,7 @@
 pub mod monitor_address;
 pub mod monitor_cex;
-pub mod monitor_ip;
\ No newline at end of file
+pub mod monitor_exchange_balance;
+pub mod monitor_funding;
+pub mod monitor_gas;
+pub mod monitor_ip;
+pub mod monitor_node;
\ No newline at end of file
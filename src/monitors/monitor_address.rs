@@ -1,52 +1,334 @@
-use crate::utils::config_struct::MonitorAddress;
-use crate::utils::messenger::send_one_message;
+use crate::utils::config_struct::{BitcoinConfig, ChainType, MonitorAddress, NodeProviderConfig, SolanaConfig};
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::messenger::escape_markdown;
+use crate::utils::address_watch::{AddressWatchRecord, AddressWatchStore};
+use crate::utils::balance_history::{burn_rate_per_hour, find_balance_drop, hours_left, BalanceHistoryStore, BalanceSample};
+use crate::blockchain::bitcoin::esplora::EsploraClient;
+use crate::blockchain::solana::client::SolanaClient;
+use crate::blockchain::solana::dto::{to_ui_amount, SOL_DECIMALS};
+use crate::blockchain::chain_registry::{ChainInfo, ChainRegistry};
+use crate::blockchain::erc20::Erc20;
 use crate::blockchain::node_provider::build_ethers_provider;
 
+use std::collections::HashMap;
+use std::env;
 use std::error::Error;
+use std::ops::RangeInclusive;
 use std::result::Result;
+use chrono::Utc;
 use ethers::{
-    core::utils::format_ether,
-    core::types::Address,
+    core::utils::format_units,
+    core::types::{Address, BlockNumber},
     providers::Middleware,
 };
 
-pub async fn addresses_balances(monitor_addresses: Vec<MonitorAddress>) {
+/// Scan at most these many blocks for outgoing transactions in one run
+const MAX_SCANNED_BLOCKS: u64 = 500;
+const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// Where the balances are read from by `MonitorAddress.chain_type`
+pub struct BalanceSources<'a> {
+    pub node_providers: &'a HashMap<String, NodeProviderConfig>,
+    pub bitcoin: &'a BitcoinConfig,
+    pub solana: &'a SolanaConfig
+}
+
+pub async fn addresses_balances(
+    monitor_addresses: Vec<MonitorAddress>,
+    chains: &ChainRegistry,
+    sources: &BalanceSources<'_>,
+    notifiers: &NotifierRegistry,
+    alert_store: &AlertStore,
+    watch_store: &AddressWatchStore,
+    history_store: &BalanceHistoryStore
+) {
+    let node_providers = sources.node_providers;
     for monitor_address in monitor_addresses {
-        match check_one_address(monitor_address.clone()).await {
-            Ok(balance) => {
+        let subject = monitor_address.alert_subject();
+        let chain = match monitor_address.chain_type {
+            ChainType::Evm => chains.get(monitor_address.chain_id),
+            ChainType::Bitcoin | ChainType::Solana => None
+        };
+        if monitor_address.is_watched() {
+            let watch_result = watch_one_address(
+                &monitor_address, chain, node_providers, notifiers, alert_store, watch_store
+            ).await;
+            if let Err(e) = watch_result {
+                eprintln!("[Watch] Failed to watch address {} ({}): {e}", monitor_address.address, monitor_address.name)
+            }
+        }
+        match check_one_address(monitor_address.clone(), chain, sources).await {
+            Ok((balance, symbol)) => {
                 let alert_threshold = monitor_address.alert_threshold;
                 let address = monitor_address.address;
                 let address_name = monitor_address.name;
-                let symbol = monitor_address.symbol;
+                let address_label = escape_markdown(&address_name);
+                let explorer_link = chain
+                    .and_then(|chain| chain.address_url(&address))
+                    .map(|url| format!(" \n🔍 {url}"))
+                    .unwrap_or_default();
+                alert_store.resolve("addresses_balances_error", &subject);
+
+                let history = history_store.history(&subject);
+                let current = BalanceSample { timestamp: Utc::now().timestamp(), balance };
+                history_store.record(&subject, current);
+
+                let balance_drop = find_balance_drop(&history, current, monitor_address.drop_window_secs)
+                    .filter(|balance_drop| {
+                        monitor_address.max_drop_percent.is_some_and(|max_drop_percent| balance_drop.percent >= max_drop_percent)
+                            || monitor_address.max_drop_amount.is_some_and(|max_drop_amount| balance_drop.amount >= max_drop_amount)
+                    });
+                match balance_drop {
+                    Some(balance_drop) => {
+                        if alert_store.should_alert("balance_drop", &subject) {
+                            let since = match monitor_address.drop_window_secs {
+                                Some(window_secs) => format!("within {window_secs} seconds"),
+                                None => "since the last check".to_string()
+                            };
+                            let message = format!(
+                                "📉 Balance dropped by __{}__ {symbol} ({:.2}%) 📉 \nThe address \n_{address}_ ({address_label}) \nbalance went from {} to ||__{balance}__|| {symbol} {since}{explorer_link}",
+                                balance_drop.amount, balance_drop.percent, balance_drop.reference.balance
+                            );
+                            notifiers.send_message(message).await
+                        }
+                    }
+                    None => {
+                        alert_store.resolve("balance_drop", &subject);
+                    }
+                }
+
                 if balance < alert_threshold {
-                    let message = format!(
-                        "‼️ Balance below __{alert_threshold}__ alert ‼️ \nThe address \n_{address}_ ({address_name}) \nbalance is ||__{balance}__|| {symbol} 💸 \nPlease top up the wallet 👛"
-                    );
-                    send_one_message(message, None, None).await
+                    if alert_store.should_alert("addresses_balances", &subject) {
+                        let runs_out = burn_rate_per_hour(&history, current)
+                            .map(|burn_rate| format!(
+                                " \nIt runs out in about __{:.1}__ hours at {burn_rate:.4} {symbol} per hour ⏳",
+                                hours_left(balance, burn_rate)
+                            ))
+                            .unwrap_or_default();
+                        let message = format!(
+                            "‼️ Balance below __{alert_threshold}__ alert ‼️ \nThe address \n_{address}_ ({address_label}) \nbalance is ||__{balance}__|| {symbol} 💸 \nPlease top up the wallet 👛{runs_out}{explorer_link}"
+                        );
+                        notifiers.send_message(message).await
+                    }
                 } else {
-                    println!("The address {address} ({address_name}) balance is {balance} {symbol}, no need to alert.\n")
+                    println!("The address {address} ({address_name}) balance is {balance} {symbol}, no need to alert.\n");
+                    if alert_store.resolve("addresses_balances", &subject) {
+                        let message = format!(
+                            "✅ Balance recovered ✅ \nThe address \n_{address}_ ({address_label}) \nbalance is back to __{balance}__ {symbol}{explorer_link}"
+                        );
+                        notifiers.send_message(message).await
+                    }
                 }
             }
             Err(e) => {
-                send_one_message(format!("Failed to check balance, please check the logs for more details."), None, None).await;
+                if alert_store.should_alert("addresses_balances_error", &subject) {
+                    notifiers.send_message("Failed to check balance, please check the logs for more details.".to_string()).await;
+                }
                 eprintln!("{e}")
             }
         }
     }
+    watch_store.flush();
+    history_store.flush();
 }
 
-async fn check_one_address(monitor_address: MonitorAddress) -> Result<f64, Box<dyn Error>> {
-    let provider = build_ethers_provider(monitor_address.chain_id);
-    let address = monitor_address.address;
+/// Returns the balance and its symbol
+async fn check_one_address(
+    monitor_address: MonitorAddress,
+    chain: Option<&ChainInfo>,
+    sources: &BalanceSources<'_>
+) -> Result<(f64, String), Box<dyn Error>> {
+    match monitor_address.chain_type {
+        ChainType::Evm => check_evm_address(monitor_address, chain, sources.node_providers).await,
+        ChainType::Bitcoin => check_bitcoin_address(monitor_address, sources.bitcoin).await,
+        ChainType::Solana => check_solana_address(monitor_address, sources.solana).await
+    }
+}
 
-    match provider.get_balance(address.parse::<Address>().unwrap(), None).await {
-        Ok(balance) => {
-            let account_balance: f64 = format_ether(balance).parse::<f64>().unwrap();
+async fn check_solana_address(monitor_address: MonitorAddress, solana: &SolanaConfig) -> Result<(f64, String), Box<dyn Error>> {
+    let rpc_url = env::var(&solana.rpc_url_env)
+        .map_err(|_| format!("Solana rpc url {} not found", solana.rpc_url_env))?;
+    let client = SolanaClient::new(rpc_url);
 
-            Ok(account_balance)
+    match monitor_address.token_contract {
+        Some(mint) => {
+            let symbol = monitor_address.symbol
+                .ok_or(format!("symbol of the SPL token {mint} is required"))?;
+            let (amount, decimals) = client.fetch_token_balance(&monitor_address.address, &mint).await?;
+            let decimals = monitor_address.decimals.or(decimals).unwrap_or(0);
+            Ok((to_ui_amount(amount, decimals), symbol))
         }
-        Err(e) => {
-            panic!("Failed to check balance of address {} ({}), error: {}", address, monitor_address.name, e.to_string())
+        None => {
+            let lamports = client.fetch_balance(&monitor_address.address).await?;
+            Ok((to_ui_amount(lamports, SOL_DECIMALS), monitor_address.symbol.unwrap_or("SOL".to_string())))
         }
     }
 }
+
+async fn check_bitcoin_address(monitor_address: MonitorAddress, bitcoin: &BitcoinConfig) -> Result<(f64, String), Box<dyn Error>> {
+    let esplora = EsploraClient::new(bitcoin.esplora_url.clone());
+    let balance = esplora.fetch_balance(
+        &monitor_address.address,
+        monitor_address.gap_limit.unwrap_or(DEFAULT_GAP_LIMIT)
+    ).await?;
+    println!(
+        "[Bitcoin] {} confirmed balance {} BTC, with unconfirmed {} BTC",
+        monitor_address.name, balance.confirmed_btc(), balance.total_btc()
+    );
+
+    let symbol = monitor_address.symbol.unwrap_or("BTC".to_string());
+    if bitcoin.include_unconfirmed {
+        Ok((balance.total_btc(), symbol))
+    } else {
+        Ok((balance.confirmed_btc(), symbol))
+    }
+}
+
+pub async fn check_evm_address(
+    monitor_address: MonitorAddress,
+    chain: Option<&ChainInfo>,
+    node_providers: &HashMap<String, NodeProviderConfig>
+) -> Result<(f64, String), Box<dyn Error>> {
+    let node = build_ethers_provider(monitor_address.chain_id, node_providers.get(&monitor_address.chain_id.to_string()))?;
+    let address = monitor_address.address.parse::<Address>()?;
+
+    if let Some(token_contract) = monitor_address.token_contract {
+        let token_contract = token_contract.parse::<Address>()?;
+        let decimals = match monitor_address.decimals {
+            Some(decimals) => decimals,
+            None => node.request(|provider| async move {
+                Ok(Erc20::new(token_contract, provider).decimals().call().await?)
+            }).await?
+        };
+        let symbol = match monitor_address.symbol {
+            Some(symbol) => symbol,
+            None => node.request(|provider| async move {
+                Ok(Erc20::new(token_contract, provider).symbol().call().await?)
+            }).await?
+        };
+        let balance = node.read(|provider, block| async move {
+            Ok(Erc20::new(token_contract, provider).balance_of(address).block(block).call().await?)
+        }).await?;
+
+        return Ok((format_units(balance, decimals as u32)?.parse::<f64>()?, symbol))
+    }
+
+    let symbol = monitor_address.symbol
+        .or(chain.map(|chain| chain.native_symbol.clone()))
+        .ok_or(format!("symbol of the unknown chain id {} is required", monitor_address.chain_id))?;
+    let decimals = monitor_address.decimals.or(chain.map(|chain| chain.decimals)).unwrap_or(18);
+    let balance = node.read(|provider, block| async move {
+        Ok(provider.get_balance(address, Some(block)).await?)
+    }).await?;
+
+    Ok((format_units(balance, decimals as u32)?.parse::<f64>()?, symbol))
+}
+
+/// Alert on outgoing transactions since the last run and on transactions pending for too long
+async fn watch_one_address(
+    monitor_address: &MonitorAddress,
+    chain: Option<&ChainInfo>,
+    node_providers: &HashMap<String, NodeProviderConfig>,
+    notifiers: &NotifierRegistry,
+    alert_store: &AlertStore,
+    watch_store: &AddressWatchStore
+) -> Result<(), Box<dyn Error>> {
+    let chain_id = monitor_address.chain_id;
+    let node = build_ethers_provider(chain_id, node_providers.get(&chain_id.to_string()))?;
+    let address = monitor_address.address.parse::<Address>()?;
+    let address_name = &monitor_address.name;
+    let address_label = escape_markdown(address_name);
+    let native_symbol = chain.map_or("native coin".to_string(), |chain| chain.native_symbol.clone());
+    let decimals = chain.map_or(18, |chain| chain.decimals);
+
+    let (latest_block, latest_nonce, pending_nonce) = node.request(|provider| async move {
+        let latest_block = provider.get_block_number().await?;
+        let latest_nonce = provider.get_transaction_count(address, Some(BlockNumber::Number(latest_block).into())).await?;
+        let pending_nonce = provider.get_transaction_count(address, Some(BlockNumber::Pending.into())).await?;
+        Ok((latest_block.as_u64(), latest_nonce.as_u64(), pending_nonce.as_u64()))
+    }).await?;
+
+    let Some(mut record) = watch_store.get(chain_id, &monitor_address.address) else {
+        println!("[Watch] Start watching {} ({address_name}) from block {latest_block}, nonce {latest_nonce}", monitor_address.address);
+        watch_store.set(chain_id, &monitor_address.address, AddressWatchRecord { last_block: latest_block, nonce: latest_nonce, pending_since: None });
+        return Ok(())
+    };
+
+    if latest_nonce > record.nonce {
+        for block_number in blocks_to_scan(record.last_block, latest_block) {
+            let block = node.request(|provider| async move {
+                Ok(provider.get_block_with_txs(block_number).await?)
+            }).await?;
+
+            // Build the whole block's alerts before sending, an error then leaves the block to the next run
+            let mut found_transactions = 0;
+            let mut messages = Vec::new();
+            for transaction in block.iter().flat_map(|block| &block.transactions).filter(|transaction| transaction.from == address) {
+                found_transactions += 1;
+                let value = format_units(transaction.value, decimals as u32)?.parse::<f64>()?;
+                let to = transaction.to.map_or("a new contract".to_string(), |to| format!("{to:?}"));
+                let tx_link = chain
+                    .and_then(|chain| chain.tx_url(&format!("{:?}", transaction.hash)))
+                    .map(|url| format!(" \n🔍 {url}"))
+                    .unwrap_or_default();
+
+                let title = match monitor_address.max_transfer_value {
+                    Some(max_transfer_value) if value > max_transfer_value =>
+                        format!("🚨 Transfer above __{max_transfer_value}__ {native_symbol} 🚨"),
+                    _ if monitor_address.watch_outgoing => "📤 Outgoing transaction 📤".to_string(),
+                    _ => continue
+                };
+                messages.push(format!(
+                    "{title} \nThe address \n_{}_ ({address_label}) \nsent __{value}__ {native_symbol} to {to} in block {block_number}{tx_link}",
+                    monitor_address.address
+                ));
+            }
+            for message in messages {
+                notifiers.send_message(message).await
+            }
+
+            // Save the progress so that a later failure doesn't send these alerts again
+            record.last_block = block_number;
+            record.nonce = (record.nonce + found_transactions).min(latest_nonce);
+            watch_store.set(chain_id, &monitor_address.address, record.clone());
+        }
+
+        // Some transactions are older than the scanned blocks
+        if monitor_address.watch_outgoing && latest_nonce > record.nonce {
+            let message = format!(
+                "📤 {} more outgoing transaction(s) 📤 \nThe address \n_{}_ ({address_label}) \nnonce went from {} to {latest_nonce}",
+                latest_nonce - record.nonce, monitor_address.address, record.nonce
+            );
+            notifiers.send_message(message).await
+        }
+    }
+
+    let subject = format!("{chain_id}:{}", monitor_address.address);
+    match (record.update_pending(latest_nonce, pending_nonce, Utc::now().timestamp()), monitor_address.max_pending_secs) {
+        (Some(pending_secs), Some(max_pending_secs)) if pending_secs > max_pending_secs as i64 => {
+            if alert_store.should_alert("address_pending", &subject) {
+                let message = format!(
+                    "⏳ Transaction pending for __{pending_secs}__ seconds ⏳ \nThe address \n_{}_ ({address_label}) \nhas {} pending transaction(s) from nonce {latest_nonce}",
+                    monitor_address.address, pending_nonce - latest_nonce
+                );
+                notifiers.send_message(message).await
+            }
+        }
+        _ => {
+            alert_store.resolve("address_pending", &subject);
+        }
+    }
+
+    // A lagging node after a failover must not move the record backwards
+    record.last_block = record.last_block.max(latest_block);
+    record.nonce = record.nonce.max(latest_nonce);
+    watch_store.set(chain_id, &monitor_address.address, record);
+    Ok(())
+}
+
+/// The blocks after `last_block`, only the latest `MAX_SCANNED_BLOCKS` of them if the gap is too large
+pub fn blocks_to_scan(last_block: u64, latest_block: u64) -> RangeInclusive<u64> {
+    let start = (last_block + 1).max(latest_block.saturating_sub(MAX_SCANNED_BLOCKS - 1));
+    start..=latest_block
+}
@@ -1,60 +1,183 @@
-use tokio::join;
-
-use crate::exchanges::{
-    okx::{actor::OkxActor},
-    hashkey::{actor::HashKeyActor},
-    mexc::{actor::MexcActor},
+use std::collections::HashMap;
+use rust_decimal::Decimal;
 
+use crate::exchanges::dto::{ArbitrageOpportunity, NetworkInfo, PriceResult};
+use crate::exchanges::registry::ExchangeRegistry;
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::config_struct::{ExchangeDifference, Exchanges, Instruments};
+use crate::utils::error::HttpError;
+use crate::utils::messenger::escape_markdown;
+use crate::utils::number_utils::{
+    find_arbitrage_opportunity, format_decimal, normalize_quotes, quote_ccys_to_normalize, sort_price_result, USD
 };
-use crate::utils::config_struct::{ExchangeDifference, Exchanges};
-use tuple_conv::RepeatedTuple;
-use crate::utils::number_utils::sort_price_result;
 
-pub async fn exchange_prices(exchange_difference: ExchangeDifference) {
-    let okx = OkxActor::new();
-    let mexc = MexcActor::new();
-    let hashkey = HashKeyActor::new();
+pub async fn exchange_prices(
+    exchange_difference: ExchangeDifference,
+    registry: &ExchangeRegistry,
+    notifiers: &NotifierRegistry,
+    alert_store: &AlertStore
+) {
+    if registry.is_empty() {
+        eprintln!("No supported exchange is configured, skip monitoring exchange prices");
+        return
+    }
+    registry.refresh_catalogs().await;
 
     for instrument in exchange_difference.instruments {
         println!(">>> Start monitoring {:?}", instrument.clone());
 
-        let all_results = join!(
-            okx.fetch_price(instrument.clone(), find_exchange_config(exchange_difference.exchanges.clone(), "OKX")),
-            hashkey.fetch_price(instrument.clone(), find_exchange_config(exchange_difference.exchanges.clone(), "HashKey")),
-            mexc.fetch_price(instrument.clone(), find_exchange_config(exchange_difference.exchanges.clone(), "MEXC")),
-        );
-
-        let sorted_results = sort_price_result(all_results.clone().to_vec());
+        let all_results = registry.fetch_prices(instrument.clone()).await;
 
-        // okx.fetch_ccy_data(instrument.clone(), find_exchange_config(exchange_difference.exchanges.clone(), "OKX")).await.expect("OKX: panic message");
-        // mexc.fetch_ccy_info(instrument.clone(), find_exchange_config(exchange_difference.exchanges.clone(), "MEXC")).await.expect("MEXC: panic message");
-        // hashkey.fetch_account(instrument.clone(), find_exchange_config(exchange_difference.exchanges.clone(), "HashKey")).await.expect("HashKey: panic message");
+        // Venues may quote the pair in another stablecoin or in USD, compare them in base_ccy
+        let quote_ccys = quote_ccys_to_normalize(&all_results, &instrument.base_ccy);
+        let all_results = if quote_ccys.is_empty() {
+            all_results
+        } else {
+            let usd_rates = registry.fetch_usd_rates(&quote_ccys).await;
+            for ccy in quote_ccys.iter() {
+                match usd_rates.get(ccy) {
+                    Some(rate) => println!("☉ {ccy}/{USD} rate → {}", format_decimal(*rate, None)),
+                    None => println!("☉ {ccy}/{USD} rate → Unknown")
+                }
+            }
+            normalize_quotes(all_results, &instrument.base_ccy, &usd_rates)
+        };
 
+        let precision = instrument.display_precision;
         println!("\nAll results: ");
-        println!("☉ OKX → {:?}", all_results.clone().0.unwrap());
-        println!("☉ HashKey → {:?}", all_results.clone().1.unwrap());
-        println!("☉ MEXC → {:?}", all_results.clone().2.unwrap());
+        for (registered, result) in registry.exchanges.iter().zip(all_results.iter()) {
+            match result {
+                Ok(price_result) => println!(
+                    "☉ {} ({}) → [Price: {}] [Buy: {}] [Sell: {}]",
+                    registered.config.name, price_result.display_instrument(),
+                    describe_price(price_result, price_result.price, price_result.normalization.as_ref().map(|raw| raw.raw_price), precision),
+                    describe_optional_price(price_result, price_result.buy_price, price_result.normalization.as_ref().and_then(|raw| raw.raw_buy_price), precision),
+                    describe_optional_price(price_result, price_result.sell_price, price_result.normalization.as_ref().and_then(|raw| raw.raw_sell_price), precision)
+                ),
+                Err(e) => println!("☉ {} → Failed: {e}", registered.config.name)
+            }
+        }
+
+        let mut network_infos: HashMap<String, NetworkInfo> = HashMap::new();
+        for (registered, result) in registry.exchanges.iter().zip(registry.fetch_ccy_infos(instrument.clone()).await) {
+            match result {
+                Ok(network_info) => {
+                    network_infos.insert(registered.exchange.data_source(), network_info);
+                }
+                // Venues without the api are treated as unknown networks
+                Err(HttpError::Unsupported) => {}
+                Err(e) => eprintln!("[{}] Failed to fetch currency info: {e}", registered.exchange.data_source())
+            }
+        }
+
+        let sorted_results = sort_price_result(all_results);
+        let opportunity = match find_arbitrage_opportunity(&sorted_results, &network_infos, instrument.notional_size) {
+            Some(opportunity) => opportunity,
+            None => {
+                eprintln!("\nNot enough executable prices of {:?} to compare\n", instrument);
+                continue
+            }
+        };
 
-        let highest_result = sorted_results.get(0).unwrap();
-        let lowest_result = sorted_results.get(sorted_results.len() - 1).unwrap();
+        println!("\nBest executable prices for {} {}: ", instrument.notional_size, instrument.base_ccy);
+        println!("☉ Buy → {} ({}) at {} [Best ask: {:?}] [Fee: {}%]",
+            opportunity.buy.data_source, opportunity.buy.display_instrument(), describe_buy_price(&opportunity, precision),
+            opportunity.buy.best_ask, opportunity.buy.trading_fee_rate
+        );
+        println!("☉ Sell → {} ({}) at {} [Best bid: {:?}] [Fee: {}%]",
+            opportunity.sell.data_source, opportunity.sell.display_instrument(), describe_sell_price(&opportunity, precision),
+            opportunity.sell.best_bid, opportunity.sell.trading_fee_rate
+        );
 
-        println!("\nSorted results: ");
-        println!("☉ The highest → {:?}", highest_result);
-        println!("☉ The lowest → {:?}", lowest_result);
+        println!("☉ Withdrawal fee → {:?} {}", opportunity.withdrawal_fee, instrument.target_ccy);
 
-        let price_difference = highest_result.price - lowest_result.price;
-        let percent_rate = price_difference / highest_result.price;
-        let percent_str = percent_rate * 100_f32;
         println!(
-            "\nHighest {} 🆚 lowest {} ({}) => [Difference: {price_difference}], [percent: {percent_str}%] \n",
-            highest_result.data_source, lowest_result.data_source, lowest_result.instrument
-        )
+            "\nBuy on {} → sell on {} => [Gross: {}], [Gross percent: {}%], [Net: {}], [Net percent: {}%]",
+            opportunity.buy.data_source, opportunity.sell.data_source,
+            format_decimal(opportunity.gross_spread, precision), format_decimal(opportunity.gross_percent, precision),
+            format_decimal(opportunity.net_spread, precision), format_decimal(opportunity.net_percent, precision)
+        );
+        match &opportunity.non_actionable_reason {
+            Some(reason) => println!("⚠️ Not actionable: {reason}\n"),
+            None => println!("✅ Actionable\n")
+        }
+
+        let alert_spread_percent = instrument.alert_spread_percent.or(exchange_difference.alert_spread_percent);
+        if let Some(alert_spread_percent) = alert_spread_percent {
+            let subject = format!("{}/{}", instrument.target_ccy, instrument.base_ccy);
+            if opportunity.is_actionable() && opportunity.net_percent >= alert_spread_percent {
+                if alert_store.should_alert("exchange_prices", &subject) {
+                    let message = build_spread_alert_message(&instrument, &opportunity, alert_spread_percent);
+                    notifiers.send_message(message).await
+                }
+            } else {
+                alert_store.resolve("exchange_prices", &subject);
+            }
+        }
     }
 }
 
-fn find_exchange_config(list: Vec<Exchanges>, name: &str) -> Exchanges {
-    return list.iter()
-        .find(|item| item.name.to_ascii_uppercase() == name.to_ascii_uppercase())
-        .expect(&*format!("Exchange config of {name} not found"))
-        .clone()
+/// Verify the api keys of the configured exchanges, only run on demand with `--check-accounts`
+pub async fn check_accounts(exchanges: Vec<Exchanges>) {
+    let registry = ExchangeRegistry::from_config(exchanges);
+    for (registered, result) in registry.exchanges.iter().zip(registry.fetch_accounts().await) {
+        if let Err(e) = result {
+            eprintln!("[{}] Failed to check account: {e}", registered.exchange.data_source())
+        }
+    }
+}
+
+pub fn build_spread_alert_message(instrument: &Instruments, opportunity: &ArbitrageOpportunity, alert_spread_percent: Decimal) -> String {
+    let precision = instrument.display_precision;
+    let target_ccy = instrument.target_ccy.to_ascii_uppercase();
+    let base_ccy = instrument.base_ccy.to_ascii_uppercase();
+    let withdrawal_fee = match opportunity.withdrawal_fee {
+        Some(withdrawal_fee) => format!("{} {target_ccy}", format_decimal(withdrawal_fee, None)),
+        None => "unknown".to_string()
+    };
+
+    let alert_spread_percent = format_decimal(alert_spread_percent, None);
+    format!(
+        "📈 {target_ccy}/{base_ccy} spread above __{alert_spread_percent}%__ 📈 \
+        \nSize: {} {base_ccy} \
+        \nBuy on *{}* (`{}`) at {} with fee {}% \
+        \nSell on *{}* (`{}`) at {} with fee {}% \
+        \nWithdrawal fee: {withdrawal_fee} \
+        \nGross spread: {} ({}%) \
+        \nNet spread: __{}__ (||{}%||) 💰",
+        format_decimal(instrument.notional_size, None),
+        escape_markdown(&opportunity.buy.data_source), opportunity.buy.display_instrument(), describe_buy_price(opportunity, precision),
+        format_decimal(opportunity.buy.trading_fee_rate, None),
+        escape_markdown(&opportunity.sell.data_source), opportunity.sell.display_instrument(), describe_sell_price(opportunity, precision),
+        format_decimal(opportunity.sell.trading_fee_rate, None),
+        format_decimal(opportunity.gross_spread, precision), format_decimal(opportunity.gross_percent, precision),
+        format_decimal(opportunity.net_spread, precision), format_decimal(opportunity.net_percent, precision)
+    )
+}
+
+fn describe_buy_price(opportunity: &ArbitrageOpportunity, precision: Option<u32>) -> String {
+    let raw_price = opportunity.buy.normalization.as_ref().and_then(|raw| raw.raw_buy_price);
+    describe_price(&opportunity.buy, opportunity.buy_price, raw_price, precision)
+}
+
+fn describe_sell_price(opportunity: &ArbitrageOpportunity, precision: Option<u32>) -> String {
+    let raw_price = opportunity.sell.normalization.as_ref().and_then(|raw| raw.raw_sell_price);
+    describe_price(&opportunity.sell, opportunity.sell_price, raw_price, precision)
+}
+
+fn describe_optional_price(price_result: &PriceResult, price: Option<Decimal>, raw_price: Option<Decimal>, precision: Option<u32>) -> String {
+    price.map_or("-".to_string(), |price| describe_price(price_result, price, raw_price, precision))
+}
+
+/// The price in the compared quote currency, followed by the raw price if the venue quotes another currency
+fn describe_price(price_result: &PriceResult, price: Decimal, raw_price: Option<Decimal>, precision: Option<u32>) -> String {
+    let price = format_decimal(price, precision);
+    match (&price_result.normalization, raw_price) {
+        (Some(normalization), Some(raw_price)) => format!(
+            "{price} (raw {} {} at {})",
+            format_decimal(raw_price, precision), normalization.raw_quote_ccy, format_decimal(normalization.rate, None)
+        ),
+        _ => price
+    }
 }
@@ -0,0 +1,81 @@
+use rust_decimal::Decimal;
+
+use crate::exchanges::dto::AssetBalance;
+use crate::exchanges::registry::ExchangeRegistry;
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::config_struct::MonitorExchangeBalance;
+use crate::utils::number_utils::format_decimal;
+
+pub async fn exchange_balances(
+    monitor_exchange_balances: Vec<MonitorExchangeBalance>,
+    registry: &ExchangeRegistry,
+    notifiers: &NotifierRegistry,
+    alert_store: &AlertStore
+) {
+    if monitor_exchange_balances.is_empty() {
+        return
+    }
+    let exchange_names: Vec<String> = monitor_exchange_balances.iter().map(|monitor| monitor.exchange.clone()).collect();
+    let all_balances = registry.fetch_balances(&exchange_names).await;
+
+    for monitor in monitor_exchange_balances {
+        let subject = monitor.alert_subject();
+        let asset = monitor.asset.to_ascii_uppercase();
+        let found = all_balances.iter()
+            .find(|(registered, _)| registered.config.name.eq_ignore_ascii_case(&monitor.exchange));
+
+        let (exchange_name, balances) = match found {
+            Some((registered, Ok(balances))) => {
+                alert_store.resolve("exchange_balances_error", &subject);
+                (registered.config.name.clone(), balances)
+            }
+            Some((registered, Err(e))) => {
+                if alert_store.should_alert("exchange_balances_error", &subject) {
+                    notifiers.send_message(format!("Failed to check {} balances, please check the logs for more details.", registered.config.name)).await;
+                }
+                eprintln!("[{}] Failed to fetch balances: {e}", registered.config.name);
+                continue
+            }
+            None => {
+                eprintln!("[{}] Exchange is not configured in exchange_difference, skip monitoring its {asset} balance", monitor.exchange);
+                continue
+            }
+        };
+
+        let balance = monitored_amount(&monitor, balances);
+        if balance < monitor.alert_threshold {
+            if alert_store.should_alert("exchange_balances", &subject) {
+                let message = build_low_balance_message(&monitor, &exchange_name, balance);
+                notifiers.send_message(message).await
+            }
+        } else {
+            println!("[{exchange_name}] {asset} balance is {balance}, no need to alert.\n");
+            if alert_store.resolve("exchange_balances", &subject) {
+                let message = format!(
+                    "✅ Exchange balance recovered ✅ \nThe *{exchange_name}* account \n{asset} balance is back to __{}__",
+                    format_decimal(balance, None)
+                );
+                notifiers.send_message(message).await
+            }
+        }
+    }
+}
+
+/// Free amount of the asset, plus the locked amount if `include_locked` is set.
+/// Exchanges leave out the assets without balance, so a missing asset is zero.
+pub fn monitored_amount(monitor: &MonitorExchangeBalance, balances: &[AssetBalance]) -> Decimal {
+    balances.iter()
+        .filter(|balance| balance.asset.eq_ignore_ascii_case(&monitor.asset))
+        .map(|balance| if monitor.include_locked { balance.total() } else { balance.free })
+        .sum()
+}
+
+pub fn build_low_balance_message(monitor: &MonitorExchangeBalance, exchange_name: &str, balance: Decimal) -> String {
+    let asset = monitor.asset.to_ascii_uppercase();
+    let kind = if monitor.include_locked { "total" } else { "free" };
+    format!(
+        "‼️ Exchange balance below __{}__ {asset} alert ‼️ \nThe *{exchange_name}* account \n{kind} {asset} balance is ||__{}__|| 💸 \nPlease top up the account 🏦",
+        format_decimal(monitor.alert_threshold, None), format_decimal(balance, None)
+    )
+}
@@ -0,0 +1,131 @@
+use chrono::DateTime;
+use rust_decimal::Decimal;
+
+use crate::exchanges::dto::FundingInfo;
+use crate::exchanges::registry::ExchangeRegistry;
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::config_struct::FundingRates;
+use crate::utils::error::HttpError;
+use crate::utils::number_utils::format_decimal;
+
+/// Decimal places of the funding rates, annualized funding and basis in percent
+const PERCENT_PRECISION: Option<u32> = Some(4);
+
+/// The perpetuals with the highest and the lowest annualized funding
+#[derive(Debug, Clone)]
+pub struct FundingDivergence {
+    pub highest: FundingInfo,
+    pub lowest: FundingInfo,
+    /// Highest minus lowest annualized funding in percent
+    pub divergence_percent: Decimal
+}
+
+pub async fn funding_rates(funding_rates: FundingRates, registry: &ExchangeRegistry, notifiers: &NotifierRegistry, alert_store: &AlertStore) {
+    if funding_rates.instruments.is_empty() {
+        return
+    }
+    if registry.is_empty() {
+        eprintln!("No supported exchange is configured, skip monitoring funding rates");
+        return
+    }
+    registry.refresh_catalogs().await;
+
+    for instrument in funding_rates.instruments.iter() {
+        let pair = format!("{}/{}", instrument.target_ccy.to_ascii_uppercase(), instrument.base_ccy.to_ascii_uppercase());
+        println!(">>> Start monitoring funding of {pair} perpetuals");
+
+        let mut fundings: Vec<FundingInfo> = vec!();
+        for (registered, result) in registry.exchanges.iter().zip(registry.fetch_fundings(instrument).await) {
+            match result {
+                Ok(funding) => {
+                    println!(
+                        "☉ {} ({}) → [Funding: {}% every {}h] [Predicted: {}] [Next: {}] [Annualized: {}%] [Mark: {}] [Index: {}] [Basis: {}]",
+                        registered.config.name, funding.instrument,
+                        format_percent(funding.funding_rate), funding.interval_hours.normalize(),
+                        funding.predicted_funding_rate.map_or("-".to_string(), |rate| format!("{}%", format_percent(rate))),
+                        funding.next_funding_time.and_then(DateTime::from_timestamp_millis).map_or("-".to_string(), |time| time.to_rfc3339()),
+                        format_decimal(funding.annualized_percent(), PERCENT_PRECISION),
+                        format_price(funding.mark_price), format_price(funding.index_price),
+                        funding.basis_percent().map_or("-".to_string(), |basis| format!("{}%", format_decimal(basis, PERCENT_PRECISION)))
+                    );
+                    fundings.push(funding)
+                }
+                // Venues without perpetuals are left out quietly
+                Err(HttpError::Unsupported) => {}
+                Err(e) => println!("☉ {} → Failed: {e}", registered.config.name)
+            }
+        }
+
+        let divergence = match find_funding_divergence(&fundings) {
+            Some(divergence) => divergence,
+            None => {
+                eprintln!("\nNot enough funding rates of {pair} to compare\n");
+                continue
+            }
+        };
+        println!(
+            "\nHighest on {} → lowest on {} => [Annualized gap: {}%]\n",
+            divergence.highest.data_source, divergence.lowest.data_source,
+            format_decimal(divergence.divergence_percent, PERCENT_PRECISION)
+        );
+
+        let alert_divergence_percent = instrument.alert_divergence_percent.or(funding_rates.alert_divergence_percent);
+        if let Some(alert_divergence_percent) = alert_divergence_percent {
+            if divergence.divergence_percent >= alert_divergence_percent {
+                if alert_store.should_alert("funding_rates", &pair) {
+                    let message = build_funding_alert_message(&pair, &divergence, alert_divergence_percent);
+                    notifiers.send_message(message).await
+                }
+            } else {
+                alert_store.resolve("funding_rates", &pair);
+            }
+        }
+    }
+}
+
+/// Needs the funding of at least two exchanges
+pub fn find_funding_divergence(fundings: &[FundingInfo]) -> Option<FundingDivergence> {
+    if fundings.len() < 2 {
+        return None
+    }
+    let highest = fundings.iter().max_by_key(|funding| funding.annualized_percent())?;
+    let lowest = fundings.iter().min_by_key(|funding| funding.annualized_percent())?;
+
+    Some(FundingDivergence {
+        highest: highest.clone(),
+        lowest: lowest.clone(),
+        divergence_percent: highest.annualized_percent() - lowest.annualized_percent()
+    })
+}
+
+pub fn build_funding_alert_message(pair: &str, divergence: &FundingDivergence, alert_divergence_percent: Decimal) -> String {
+    let describe = |funding: &FundingInfo| format!(
+        "*{}* (`{}`) at {}% every {}h, __{}%__ a year, basis {}",
+        funding.data_source, funding.instrument,
+        format_percent(funding.funding_rate), funding.interval_hours.normalize(),
+        format_decimal(funding.annualized_percent(), Some(2)),
+        funding.basis_percent().map_or("unknown".to_string(), |basis| format!("{}%", format_decimal(basis, PERCENT_PRECISION)))
+    );
+
+    format!(
+        "💸 {pair} funding gap above __{}%__ a year 💸 \
+        \nHighest: {} \
+        \nLowest: {} \
+        \nGap: __{}%__ a year, short on {} and long on {}",
+        format_decimal(alert_divergence_percent, None),
+        describe(&divergence.highest),
+        describe(&divergence.lowest),
+        format_decimal(divergence.divergence_percent, Some(2)),
+        divergence.highest.data_source, divergence.lowest.data_source
+    )
+}
+
+fn format_price(price: Option<Decimal>) -> String {
+    price.map_or("-".to_string(), |price| format_decimal(price, None))
+}
+
+/// Funding rate fraction in percent
+fn format_percent(rate: Decimal) -> String {
+    format_decimal(rate * Decimal::ONE_HUNDRED, PERCENT_PRECISION)
+}
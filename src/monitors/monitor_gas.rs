@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::error::Error;
+use ethers::core::types::{BlockNumber, U256};
+use ethers::core::utils::format_units;
+use ethers::providers::Middleware;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::blockchain::chain_registry::ChainRegistry;
+use crate::blockchain::node_provider::build_ethers_provider;
+use crate::exchanges::registry::ExchangeRegistry;
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::config_struct::{Instruments, MonitorGasPrice, NodeProviderConfig};
+use crate::utils::number_utils::{median, normalize_quotes, quote_ccys_to_normalize, USD};
+
+/// Gas of a plain native coin transfer
+const TRANSFER_GAS: f64 = 21000.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasPrice {
+    pub base_fee_gwei: f64,
+    pub priority_fee_gwei: f64
+}
+
+impl GasPrice {
+    pub fn total_gwei(&self) -> f64 {
+        self.base_fee_gwei + self.priority_fee_gwei
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GasLevel {
+    Below(f64),
+    Above(f64)
+}
+
+pub async fn gas_prices(
+    monitor_gas_prices: Vec<MonitorGasPrice>,
+    registry: &ExchangeRegistry,
+    chains: &ChainRegistry,
+    node_providers: &HashMap<String, NodeProviderConfig>,
+    notifiers: &NotifierRegistry,
+    alert_store: &AlertStore
+) {
+    for monitor_gas_price in monitor_gas_prices {
+        let chain_id = monitor_gas_price.chain_id;
+        let chain = chains.get(chain_id);
+        let chain_name = chain.map_or(format!("chain id {chain_id}"), |chain| chain.name.clone());
+
+        let gas_price = match fetch_gas_price(chain_id, node_providers.get(&chain_id.to_string())).await {
+            Ok(gas_price) => gas_price,
+            Err(e) => {
+                eprintln!("[Gas] Failed to fetch gas price of {chain_name}: {e}\n");
+                continue
+            }
+        };
+        let total_gwei = gas_price.total_gwei();
+        println!(
+            "[Gas] {chain_name} base fee {:.3} gwei, priority fee {:.3} gwei",
+            gas_price.base_fee_gwei, gas_price.priority_fee_gwei
+        );
+
+        let gas_level = check_gas_price(&monitor_gas_price, total_gwei);
+        let (below_subject, above_subject) = (format!("{chain_id}:below"), format!("{chain_id}:above"));
+        let (subject, title) = match gas_level {
+            Some(GasLevel::Below(threshold)) => (below_subject, format!("⛽ {chain_name} gas below __{threshold}__ gwei ⛽")),
+            Some(GasLevel::Above(threshold)) => (above_subject, format!("🔥 {chain_name} gas above __{threshold}__ gwei 🔥")),
+            None => {
+                println!("[Gas] {chain_name} gas is {total_gwei:.3} gwei, no need to alert.\n");
+                alert_store.resolve("gas_prices", &below_subject);
+                alert_store.resolve("gas_prices", &above_subject);
+                continue
+            }
+        };
+        if !alert_store.should_alert("gas_prices", &subject) {
+            continue
+        }
+
+        let native_symbol = chain.map(|chain| chain.native_symbol.clone());
+        let transfer_cost = match &native_symbol {
+            Some(native_symbol) => match fetch_usd_price(registry, native_symbol).await {
+                Some(usd_price) => format!(
+                    " \nA transfer costs about __{:.4}__ USD ({native_symbol} at {usd_price} USD)",
+                    transfer_cost_usd(total_gwei, usd_price)
+                ),
+                None => String::new()
+            },
+            None => String::new()
+        };
+        let message = format!(
+            "{title} \nGas is __{total_gwei:.3}__ gwei \nbase fee {:.3} gwei, priority fee {:.3} gwei{transfer_cost}",
+            gas_price.base_fee_gwei, gas_price.priority_fee_gwei
+        );
+        notifiers.send_message(message).await
+    }
+}
+
+/// The lower threshold is checked first if both are crossed because of a misconfiguration
+pub fn check_gas_price(monitor_gas_price: &MonitorGasPrice, total_gwei: f64) -> Option<GasLevel> {
+    match (monitor_gas_price.below_gwei, monitor_gas_price.above_gwei) {
+        (Some(below_gwei), _) if total_gwei < below_gwei => Some(GasLevel::Below(below_gwei)),
+        (_, Some(above_gwei)) if total_gwei > above_gwei => Some(GasLevel::Above(above_gwei)),
+        _ => None
+    }
+}
+
+pub fn transfer_cost_usd(total_gwei: f64, native_usd_price: Decimal) -> f64 {
+    TRANSFER_GAS * total_gwei / 1e9 * native_usd_price.to_f64().unwrap_or_default()
+}
+
+async fn fetch_gas_price(chain_id: u32, config: Option<&NodeProviderConfig>) -> Result<GasPrice, Box<dyn Error>> {
+    let node = build_ethers_provider(chain_id, config)?;
+    let (base_fee, priority_fee) = node.request(|provider| async move {
+        let block = provider.get_block(BlockNumber::Latest).await?.ok_or("latest block not found")?;
+        match block.base_fee_per_gas {
+            Some(base_fee) => {
+                let (_, priority_fee) = provider.estimate_eip1559_fees(None).await?;
+                Ok((base_fee, priority_fee))
+            }
+            // Legacy chains only have one gas price
+            None => Ok((provider.get_gas_price().await?, U256::zero()))
+        }
+    }).await?;
+
+    Ok(GasPrice {
+        base_fee_gwei: format_units(base_fee, "gwei")?.parse::<f64>()?,
+        priority_fee_gwei: format_units(priority_fee, "gwei")?.parse::<f64>()?
+    })
+}
+
+/// Mid price of the coin in USD, the median over the exchanges that list it.
+/// Venues without the USD pair quote it in USDT or USDC, which is converted with their USD rates.
+async fn fetch_usd_price(registry: &ExchangeRegistry, symbol: &str) -> Option<Decimal> {
+    registry.refresh_catalogs().await;
+    let all_results = registry.fetch_prices(Instruments::spot(symbol, USD, Decimal::ONE_HUNDRED)).await;
+    let usd_rates = registry.fetch_usd_rates(&quote_ccys_to_normalize(&all_results, USD)).await;
+
+    let mid_prices = normalize_quotes(all_results, USD, &usd_rates)
+        .into_iter()
+        .filter_map(|result| result.ok()?.mid_price())
+        .collect();
+    median(mid_prices).filter(|price| *price > Decimal::ZERO)
+}
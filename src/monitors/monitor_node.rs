@@ -0,0 +1,161 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use chrono::Utc;
+use ethers::core::types::{BlockNumber, SyncingStatus};
+use ethers::providers::{Http, Middleware, Provider};
+use tokio::time::{timeout, Duration, Instant};
+
+use crate::blockchain::chain_registry::{ChainInfo, ChainRegistry};
+use crate::blockchain::node_provider::{build_ethers_provider, NodeResult};
+use crate::notifiers::registry::NotifierRegistry;
+use crate::utils::alert_state::AlertStore;
+use crate::utils::config_struct::{ChainType, MonitorAddress, NodeProviderConfig};
+
+#[derive(Debug, Clone)]
+pub struct NodeStatus {
+    pub chain_id: u64,
+    pub block_number: u64,
+    /// Unix timestamp in seconds of the latest block
+    pub block_timestamp: i64,
+    pub syncing: bool,
+    pub latency: Duration
+}
+
+/// Check every endpoint of the chains in `monitor_addresses` and `[node_providers]`
+pub async fn nodes_health(
+    monitor_addresses: &[MonitorAddress],
+    chains: &ChainRegistry,
+    node_providers: &HashMap<String, NodeProviderConfig>,
+    notifiers: &NotifierRegistry,
+    alert_store: &AlertStore
+) {
+    for chain_id in monitored_chain_ids(monitor_addresses, node_providers) {
+        let chain = chains.get(chain_id);
+        let chain_name = chain.map_or(format!("chain id {chain_id}"), |chain| chain.name.clone());
+        let node_config = node_providers.get(&chain_id.to_string());
+
+        let node = match build_ethers_provider(chain_id, node_config) {
+            Ok(node) => node,
+            Err(e) => {
+                eprintln!("[Node] {e}");
+                if alert_store.should_alert("nodes_health", &chain_id.to_string()) {
+                    notifiers.send_message(format!("🩺 {chain_name} node is misconfigured 🩺 \n{e}")).await
+                }
+                continue
+            }
+        };
+        alert_store.resolve("nodes_health", &chain_id.to_string());
+
+        let max_block_age_secs = node_config
+            .and_then(|config| config.max_block_age_secs)
+            .unwrap_or_else(|| default_max_block_age_secs(chain));
+        let max_latency_ms = node_config.and_then(|config| config.max_latency_ms);
+
+        for endpoint in &node.endpoints {
+            let endpoint_name = &endpoint.name;
+            let subject = format!("{chain_id}:{endpoint_name}");
+            let problems = match timeout(node.timeout, fetch_node_status(endpoint.provider.clone())).await {
+                Ok(Ok(status)) => {
+                    println!(
+                        "[Node] {chain_name} {endpoint_name} block {} at {}, syncing: {}, latency: {:?}",
+                        status.block_number, status.block_timestamp, status.syncing, status.latency
+                    );
+                    check_node_status(chain_id, &status, max_block_age_secs, max_latency_ms, Utc::now().timestamp())
+                }
+                Ok(Err(e)) => {
+                    // The error may contain the node url, so it's only logged
+                    eprintln!("[Node] {chain_name} {endpoint_name} failed: {e}");
+                    vec!("request failed, please check the logs".to_string())
+                }
+                Err(_) => vec!(format!("no response within {} seconds", node.timeout.as_secs()))
+            };
+
+            if problems.is_empty() {
+                println!("[Node] {chain_name} {endpoint_name} is healthy\n");
+                if alert_store.resolve("nodes_health", &subject) {
+                    notifiers.send_message(format!("✅ {chain_name} node `{endpoint_name}` is healthy again ✅")).await
+                }
+            } else {
+                eprintln!("[Node] {chain_name} {endpoint_name} is unhealthy: {}\n", problems.join(", "));
+                if alert_store.should_alert("nodes_health", &subject) {
+                    let message = format!(
+                        "🩺 {chain_name} node `{endpoint_name}` is unhealthy 🩺 \n{}",
+                        problems.iter().map(|problem| format!("• {problem}")).collect::<Vec<String>>().join(" \n")
+                    );
+                    notifiers.send_message(message).await
+                }
+            }
+        }
+    }
+}
+
+/// Returns what is wrong with the node, empty if it's healthy
+pub fn check_node_status(
+    expected_chain_id: u32,
+    status: &NodeStatus,
+    max_block_age_secs: u64,
+    max_latency_ms: Option<u64>,
+    now: i64
+) -> Vec<String> {
+    let mut problems: Vec<String> = vec!();
+
+    if status.chain_id != expected_chain_id as u64 {
+        problems.push(format!("chain id is {}, expected {expected_chain_id}", status.chain_id));
+    }
+
+    let block_age_secs = now - status.block_timestamp;
+    if block_age_secs > max_block_age_secs as i64 {
+        problems.push(format!("latest block {} is {block_age_secs} seconds old", status.block_number));
+    }
+
+    if status.syncing {
+        problems.push("node is still syncing".to_string());
+    }
+
+    if let Some(max_latency_ms) = max_latency_ms {
+        let latency_ms = status.latency.as_millis();
+        if latency_ms > max_latency_ms as u128 {
+            problems.push(format!("latency {latency_ms}ms is above {max_latency_ms}ms"));
+        }
+    }
+
+    problems
+}
+
+/// 20 blocks behind, but at least one minute
+pub fn default_max_block_age_secs(chain: Option<&ChainInfo>) -> u64 {
+    let block_time_secs = chain.map_or(12.0, |chain| chain.block_time_secs);
+    (block_time_secs * 20.0).max(60.0) as u64
+}
+
+fn monitored_chain_ids(monitor_addresses: &[MonitorAddress], node_providers: &HashMap<String, NodeProviderConfig>) -> BTreeSet<u32> {
+    let mut chain_ids: BTreeSet<u32> = monitor_addresses.iter()
+        .filter(|monitor_address| monitor_address.chain_type == ChainType::Evm)
+        .map(|monitor_address| monitor_address.chain_id)
+        .collect();
+    chain_ids.extend(node_providers.keys().filter_map(|chain_id| chain_id.parse::<u32>().ok()));
+    chain_ids
+}
+
+async fn fetch_node_status(provider: Arc<Provider<Http>>) -> NodeResult<NodeStatus> {
+    let started = Instant::now();
+    let block = provider.get_block(BlockNumber::Latest).await?.ok_or("latest block not found")?;
+    let latency = started.elapsed();
+
+    let chain_id = provider.get_chainid().await?;
+    let syncing = match provider.syncing().await {
+        Ok(syncing_status) => !matches!(syncing_status, SyncingStatus::IsFalse),
+        Err(e) => {
+            eprintln!("[Node] eth_syncing is not supported: {e}");
+            false
+        }
+    };
+
+    Ok(NodeStatus {
+        chain_id: chain_id.as_u64(),
+        block_number: block.number.map_or(0, |number| number.as_u64()),
+        block_timestamp: block.timestamp.as_u64() as i64,
+        syncing,
+        latency
+    })
+}
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use crate::notifiers::notifier::Notifier;
+use crate::notifiers::webhook::post_webhook;
+use crate::utils::error::HttpError;
+use crate::utils::messenger::replace_markers;
+
+/// Discord markdown has the same underline and spoiler markers as Telegram, but `*x*` is italic so bold becomes `**x**`
+/// Reference: https://discord.com/developers/docs/resources/webhook#execute-webhook
+pub struct DiscordNotifier {
+    pub webhook_url_env: String
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url_env: String) -> Self {
+        Self { webhook_url_env }
+    }
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> String {
+        "Discord".to_string()
+    }
+
+    async fn send_message(&self, message: String) -> Result<(), HttpError> {
+        let body = json!({ "content": to_discord_markdown(message) });
+        post_webhook(self.name(), &self.webhook_url_env, body).await
+    }
+}
+
+/// Discord takes `*` as italic, double it to keep the Telegram bold
+pub fn to_discord_markdown(message: String) -> String {
+    replace_markers(&message, |marker| match marker {
+        "*" => "**",
+        marker => marker
+    })
+}
@@ -0,0 +1,6 @@
+pub mod notifier;
+pub mod registry;
+pub mod telegram;
+pub mod discord;
+pub mod slack;
+pub mod webhook;
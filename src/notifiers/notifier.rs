@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::utils::error::HttpError;
+
+#[cfg(test)]
+use mockall::automock;
+
+/// A channel that alerts are sent to, messages are written in Telegram MarkdownV2 flavour.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> String;
+
+    async fn send_message(&self, message: String) -> Result<(), HttpError>;
+}
@@ -0,0 +1,57 @@
+use futures::future::join_all;
+
+use crate::notifiers::{
+    discord::DiscordNotifier,
+    notifier::Notifier,
+    slack::SlackNotifier,
+    telegram::TelegramNotifier,
+    webhook::WebhookNotifier
+};
+use crate::utils::config_struct::NotifierChannel;
+
+/// Holds the notifier channels configured in config.toml, every message is sent to all of them.
+#[derive(Default)]
+pub struct NotifierRegistry {
+    pub notifiers: Vec<Box<dyn Notifier>>
+}
+
+impl NotifierRegistry {
+    pub fn from_config(channels: Vec<NotifierChannel>) -> Self {
+        let mut registry = Self::default();
+
+        for channel in channels {
+            match build_notifier(channel.clone()) {
+                Some(notifier) => registry.register(notifier),
+                None => eprintln!("[Notifier] Channel {:?} is not supported, skipped", channel)
+            }
+        }
+
+        registry
+    }
+
+    pub fn register(&mut self, notifier: Box<dyn Notifier>) {
+        self.notifiers.push(notifier);
+    }
+
+    /// Send the message to all channels concurrently, a failed channel doesn't stop the others
+    pub async fn send_message(&self, message: String) {
+        let futures = self.notifiers.iter().map(|notifier| notifier.send_message(message.clone()));
+
+        for (notifier, result) in self.notifiers.iter().zip(join_all(futures).await) {
+            if let Err(e) = result {
+                eprintln!("[{}] Failed to send message: {e}", notifier.name())
+            }
+        }
+    }
+}
+
+/// Add new channels here after implementing `Notifier`
+fn build_notifier(channel: NotifierChannel) -> Option<Box<dyn Notifier>> {
+    match (channel.channel.to_ascii_lowercase().as_str(), channel.webhook_url_env) {
+        ("telegram", _) => Some(Box::new(TelegramNotifier::new(channel.chat_id_env, channel.token_env))),
+        ("discord", Some(webhook_url_env)) => Some(Box::new(DiscordNotifier::new(webhook_url_env))),
+        ("slack", Some(webhook_url_env)) => Some(Box::new(SlackNotifier::new(webhook_url_env))),
+        ("webhook", Some(webhook_url_env)) => Some(Box::new(WebhookNotifier::new(webhook_url_env))),
+        _ => None
+    }
+}
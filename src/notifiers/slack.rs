@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use serde_json::json;
+
+use crate::notifiers::notifier::Notifier;
+use crate::notifiers::webhook::post_webhook;
+use crate::utils::error::HttpError;
+use crate::utils::messenger::replace_markers;
+
+/// Reference: https://api.slack.com/messaging/webhooks
+pub struct SlackNotifier {
+    pub webhook_url_env: String
+}
+
+impl SlackNotifier {
+    pub fn new(webhook_url_env: String) -> Self {
+        Self { webhook_url_env }
+    }
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn name(&self) -> String {
+        "Slack".to_string()
+    }
+
+    async fn send_message(&self, message: String) -> Result<(), HttpError> {
+        let body = json!({ "text": to_slack_mrkdwn(message) });
+        post_webhook(self.name(), &self.webhook_url_env, body).await
+    }
+}
+
+/// Slack has no underline nor spoiler, use italic instead
+pub fn to_slack_mrkdwn(message: String) -> String {
+    replace_markers(&message, |marker| match marker {
+        "__" => "_",
+        "||" => "",
+        marker => marker
+    })
+}
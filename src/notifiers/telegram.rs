@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use crate::notifiers::notifier::Notifier;
+use crate::utils::error::HttpError;
+use crate::utils::messenger::send_one_message;
+
+pub struct TelegramNotifier {
+    /// Environment variable name of the chat id, default TELEGRAM_CHAT_ID
+    pub chat_id_env: Option<String>,
+    /// Environment variable name of the bot token, default TELEGRAM_TOKEN
+    pub token_env: Option<String>
+}
+
+impl TelegramNotifier {
+    pub fn new(chat_id_env: Option<String>, token_env: Option<String>) -> Self {
+        Self { chat_id_env, token_env }
+    }
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> String {
+        "Telegram".to_string()
+    }
+
+    async fn send_message(&self, message: String) -> Result<(), HttpError> {
+        send_one_message(message, self.chat_id_env.clone(), self.token_env.clone()).await
+    }
+}
@@ -0,0 +1,56 @@
+use std::env;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::notifiers::notifier::Notifier;
+use crate::utils::error::HttpError;
+use crate::utils::http_client::HttpClient;
+use crate::utils::messenger::replace_markers;
+
+/// Posts `{"message": "..."}` in plain text to any url
+pub struct WebhookNotifier {
+    pub webhook_url_env: String
+}
+
+impl WebhookNotifier {
+    pub fn new(webhook_url_env: String) -> Self {
+        Self { webhook_url_env }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> String {
+        "Webhook".to_string()
+    }
+
+    async fn send_message(&self, message: String) -> Result<(), HttpError> {
+        let body = json!({ "message": to_plain_text(message) });
+        post_webhook(self.name(), &self.webhook_url_env, body).await
+    }
+}
+
+/// Remove the Telegram MarkdownV2 underline, spoiler and bold markers and the escapes
+pub fn to_plain_text(message: String) -> String {
+    replace_markers(&message, |marker| match marker {
+        "__" | "||" | "*" => "",
+        marker => marker
+    })
+}
+
+/// The webhook url is read when sending, a missing variable fails the message instead of the startup
+pub async fn post_webhook(name: String, webhook_url_env: &str, body: Value) -> Result<(), HttpError> {
+    let webhook_url = env::var(webhook_url_env).map_err(|_| HttpError::MissingApiKey(webhook_url_env.to_string()))?;
+    println!("[{name}] Sending one message >> {body}\n");
+
+    let client = HttpClient::new(name.clone());
+    let response = client.post_json(webhook_url, &body).await?;
+
+    if response.status().is_success() {
+        println!("[{name}] Message sent successfully!");
+        Ok(())
+    } else {
+        eprintln!("[{name}] Failed to send message. Status: {:?}", response);
+        Err(HttpError::ResponseError)
+    }
+}
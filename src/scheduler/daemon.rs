@@ -0,0 +1,99 @@
+use std::panic::AssertUnwindSafe;
+use chrono::Utc;
+use futures::future::{join_all, FutureExt, LocalBoxFuture};
+use rand::Rng;
+use tokio::sync::watch;
+use tokio::time::{sleep, Duration};
+
+use crate::utils::config_struct::MonitorSchedule;
+
+pub struct ScheduledJob<'a> {
+    pub name: String,
+    pub schedule: MonitorSchedule,
+    pub job: Box<dyn Fn() -> LocalBoxFuture<'a, ()> + 'a>
+}
+
+/// Run every job on its own schedule until SIGINT or SIGTERM is received,
+/// a running job is always allowed to finish before shutting down.
+pub async fn run_daemon(jobs: Vec<ScheduledJob<'_>>) {
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+
+    let job_loops = join_all(
+        jobs.iter().map(|job| run_job(job, shutdown_receiver.clone()))
+    );
+
+    tokio::select! {
+        _ = job_loops => println!("[Scheduler] No more jobs to run"),
+        _ = async {
+            wait_for_shutdown_signal().await;
+            println!("[Scheduler] Shutting down, waiting for running jobs to finish...");
+            shutdown_sender.send(true).ok();
+            // Keep polling until the job loops observe the signal
+            std::future::pending::<()>().await
+        } => {}
+    }
+}
+
+async fn run_job(job: &ScheduledJob<'_>, mut shutdown_receiver: watch::Receiver<bool>) {
+    let name = job.name.clone();
+    let mut next_run = match job.schedule.first_run(Utc::now()) {
+        Some(next_run) => next_run,
+        None => {
+            eprintln!("[Scheduler] {name} has neither a valid interval nor cron, skipped");
+            return
+        }
+    };
+
+    loop {
+        let jitter_millis = if job.schedule.jitter_secs > 0 {
+            rand::thread_rng().gen_range(0..job.schedule.jitter_secs * 1000)
+        } else {
+            0
+        };
+        let wait = (next_run - Utc::now()).to_std().unwrap_or_default() + Duration::from_millis(jitter_millis);
+        println!("[Scheduler] {name} will run at {next_run} (+{jitter_millis}ms jitter)");
+
+        tokio::select! {
+            _ = sleep(wait) => {},
+            _ = shutdown_receiver.changed() => {
+                println!("[Scheduler] {name} stopped");
+                return
+            }
+        }
+
+        // A panicking monitor shouldn't take the whole daemon down
+        if AssertUnwindSafe((job.job)()).catch_unwind().await.is_err() {
+            eprintln!("[Scheduler] {name} panicked, will retry on the next tick");
+        }
+
+        if *shutdown_receiver.borrow() {
+            println!("[Scheduler] {name} stopped");
+            return
+        }
+
+        match job.schedule.next_run_after(next_run, Utc::now()) {
+            Some((upcoming, skipped_ticks)) => {
+                if skipped_ticks > 0 {
+                    println!("[Scheduler] {name} was still running, skipped {skipped_ticks} tick(s)");
+                }
+                next_run = upcoming;
+            }
+            None => return
+        }
+    }
+}
+
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen to SIGTERM");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {},
+            _ = sigterm.recv() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    tokio::signal::ctrl_c().await.expect("Failed to listen to SIGINT");
+}
@@ -0,0 +1,46 @@
+use std::str::FromStr;
+use chrono::{DateTime, Duration, Utc};
+use cron::Schedule;
+
+use crate::utils::config_struct::MonitorSchedule;
+
+impl MonitorSchedule {
+    /// The first run time, interval schedules start immediately while cron schedules wait for the next match
+    pub fn first_run(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.interval_secs.is_some() {
+            Some(now)
+        } else {
+            self.next_run(now)
+        }
+    }
+
+    /// The run time right after `previous`, `None` if neither interval nor a valid cron expression is configured
+    pub fn next_run(&self, previous: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if let Some(interval_secs) = self.interval_secs {
+            return Some(previous + Duration::seconds(interval_secs.max(1) as i64))
+        }
+
+        let expression = self.cron.as_ref()?;
+        match Schedule::from_str(expression) {
+            Ok(schedule) => schedule.after(&previous).next(),
+            Err(e) => {
+                eprintln!("[Scheduler] Invalid cron expression {expression}: {e}");
+                None
+            }
+        }
+    }
+
+    /// The next run time after a run that finished at `finished`,
+    /// with the number of ticks skipped because the previous run was still going
+    pub fn next_run_after(&self, previous: DateTime<Utc>, finished: DateTime<Utc>) -> Option<(DateTime<Utc>, u32)> {
+        let mut upcoming = self.next_run(previous)?;
+        let mut skipped_ticks = 0;
+
+        while upcoming <= finished {
+            skipped_ticks += 1;
+            upcoming = self.next_run(upcoming)?;
+        }
+
+        Some((upcoming, skipped_ticks))
+    }
+}
@@ -1,15 +1,27 @@
 #[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+#[cfg(test)]
 use crate::exchanges::{
+  binance::actor::BinanceActor,
   hashkey::actor::MockHashKeyActor,
-  mexc::actor::MockMexcActor,
-  okx::actor::MockOkxActor,
-  dto::PriceResult
+  bybit::{actor::BybitActor, dto::ChainInfo},
+  mexc::{actor::MockMexcActor, dto::{ContractResponse, ContractTicker}},
+  okx::{actor::MockOkxActor, dto::CcyData},
+  dto::PriceResult,
+  symbol::{InstrumentInfo, MarketType}
 };
 #[cfg(test)]
+use rust_decimal_macros::dec;
+#[cfg(test)]
 use crate::utils::{
   config_struct::{Exchanges, Instruments},
+  error::HttpError,
   number_utils::sort_price_result
 };
+#[cfg(test)]
+use crate::unittest::http_stub::start_http_stub;
 
 
 #[tokio::test]
@@ -19,16 +31,23 @@ async fn test_fetch_price() {
   let inst = Instruments {
     base_ccy: "USDC".to_string(),
     target_ccy: "BTC".to_string(),
-    withdrawal_chain: "Bitcoin".to_string()
+    withdrawal_chain: "Bitcoin".to_string(),
+    market_type: MarketType::Spot,
+    market_type_overrides: HashMap::new(),
+    notional_size: dec!(1000.0),
+    alert_spread_percent: None,
+    display_precision: None,
+    symbol_overrides: HashMap::new()
   };
 
   let url = "https://some-url.org".to_string();
+  let symbol = InstrumentInfo::new("BTCUSDC", "BTC", "USDC", MarketType::Spot);
 
   let ctx_hashkey = MockHashKeyActor::new_context();
   ctx_hashkey.expect().returning(|| {
     let mut mock = MockHashKeyActor::default();
     let result = Ok(
-      PriceResult { data_source: "HashKey".to_string(), instrument: "BTCUSDC".to_string(), price: 5.4 }
+      PriceResult { data_source: "HashKey".to_string(), instrument: "BTCUSDC".to_string(), price: dec!(5.4), ..Default::default() }
     );
     mock.expect_fetch_price().return_const(result);
     mock
@@ -38,7 +57,7 @@ async fn test_fetch_price() {
   ctx_okx.expect().returning(|| {
     let mut mock = MockOkxActor::default();
     let result = Ok(
-      PriceResult { data_source: "OKX".to_string(), instrument: "BTCUSDC".to_string(), price: 4.1 }
+      PriceResult { data_source: "OKX".to_string(), instrument: "BTCUSDC".to_string(), price: dec!(4.1), ..Default::default() }
     );
     mock.expect_fetch_price().return_const(result);
     mock
@@ -48,7 +67,7 @@ async fn test_fetch_price() {
   ctx_mexc.expect().returning(|| {
     let mut mock = MockMexcActor::default();
     let result = Ok(
-      PriceResult { data_source: "MEXC".to_string(), instrument: "BTCUSDC".to_string(), price: 3.2 }
+      PriceResult { data_source: "MEXC".to_string(), instrument: "BTCUSDC".to_string(), price: dec!(3.2), ..Default::default() }
     );
     mock.expect_fetch_price().return_const(result);
     mock
@@ -58,17 +77,89 @@ async fn test_fetch_price() {
   let mock_okx = MockOkxActor::new();
   let mock_mexc = MockMexcActor::new();
 
-  let hashkey_result = mock_hashkey.fetch_price(inst.clone(), Exchanges { name: "HashKey".to_string(), trading_fee_rate: 0.0, url: url.clone() }).await.unwrap();
-  let okx_result = mock_okx.fetch_price(inst.clone(), Exchanges { name: "OKX".to_string(), trading_fee_rate: 0.0, url: url.clone() }).await.unwrap();
-  let mexc_result = mock_mexc.fetch_price(inst.clone(), Exchanges { name: "MEXC".to_string(), trading_fee_rate: 0.0, url: url.clone() }).await.unwrap();
+  let hashkey_result = mock_hashkey.fetch_price(inst.clone(), symbol.clone(), Exchanges { name: "HashKey".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }).await.unwrap();
+  let okx_result = mock_okx.fetch_price(inst.clone(), symbol.clone(), Exchanges { name: "OKX".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }).await.unwrap();
+  let mexc_result = mock_mexc.fetch_price(inst.clone(), symbol.clone(), Exchanges { name: "MEXC".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }).await.unwrap();
 
-  assert_eq!(5.4, hashkey_result.price);
-  assert_eq!(4.1, okx_result.price);
-  assert_eq!(3.2, mexc_result.price);
+  assert_eq!(dec!(5.4), hashkey_result.price);
+  assert_eq!(dec!(4.1), okx_result.price);
+  assert_eq!(dec!(3.2), mexc_result.price);
 
   let sorted_results = sort_price_result(vec!(Ok(okx_result), Ok(mexc_result), Ok(hashkey_result)));
 
-  assert_eq!("HashKey", sorted_results.get(0).unwrap().data_source);
+  assert_eq!("HashKey", sorted_results.first().unwrap().data_source);
   assert_eq!("OKX", sorted_results.get(sorted_results.len() - 2).unwrap().data_source);
-  assert_eq!("MEXC", sorted_results.get(sorted_results.len() - 1).unwrap().data_source);
+  assert_eq!("MEXC", sorted_results.last().unwrap().data_source);
+}
+
+#[tokio::test]
+async fn test_signed_api_without_api_keys() {
+  let binance = BinanceActor { api_key: None, secret_key: None, data_source: "Binance".to_string() };
+  let config = Exchanges { name: "Binance".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None };
+
+  let result = binance.fetch_account(config).await;
+  assert!(matches!(result, Err(HttpError::MissingApiKey(keys)) if keys == "BINANCE_API_KEY and BINANCE_SECRET_KEY"));
+}
+
+#[test]
+fn test_mexc_contract_error_response() {
+  let response: ContractResponse<ContractTicker> = serde_json::from_str(
+    r#"{"success":false,"code":1001,"message":"contract not exists"}"#
+  ).unwrap();
+  assert!(matches!(response.into_data("MEXC"), Err(HttpError::ResponseDataError)));
+
+  let response: ContractResponse<ContractTicker> = serde_json::from_str(
+    r#"{"success":true,"code":0,"data":{"symbol":"BTC_USDT","lastPrice":65000.5,"fairPrice":65001,"indexPrice":64999.8}}"#
+  ).unwrap();
+  assert_eq!("BTC_USDT", response.into_data("MEXC").unwrap().symbol);
+}
+
+#[test]
+fn test_withdrawal_network_match() {
+  let okx_chains: Vec<CcyData> = serde_json::from_str(r#"[
+    {"canWd": true, "canDep": true, "ccy": "ETH", "chain": "ETH-Arbitrum One", "minWd": "0.001", "minFee": "0.0001", "maxFee": "0.0002", "minWdUnlockConfirm": "10", "mainNet": false},
+    {"canWd": true, "canDep": true, "ccy": "ETH", "chain": "ETH-Ethereum", "minWd": "0.001", "minFee": "0.001", "maxFee": "0.002", "minWdUnlockConfirm": "64", "mainNet": true}
+  ]"#).unwrap();
+  let okx_chain = okx_chains.iter().find(|item| item.is_network("Ethereum"));
+  assert_eq!(Some("ETH-Ethereum"), okx_chain.map(|item| item.chain.as_str()));
+  assert!(!okx_chains.iter().any(|item| item.is_network("Optimism")));
+
+  let bybit_chains: Vec<ChainInfo> = serde_json::from_str(r#"[
+    {"chain": "ARBI", "chainType": "Arbitrum One", "withdrawFee": "0.0001", "withdrawMin": "0.001", "chainDeposit": "1", "chainWithdraw": "1", "confirmation": "10"},
+    {"chain": "ETH", "chainType": "Ethereum", "withdrawFee": "0.001", "withdrawMin": "0.001", "chainDeposit": "1", "chainWithdraw": "1", "confirmation": "64"}
+  ]"#).unwrap();
+  assert_eq!(Some("ETH"), bybit_chains.iter().find(|item| item.is_network("Ethereum")).map(|item| item.chain.as_str()));
+  assert_eq!(Some("ARBI"), bybit_chains.iter().find(|item| item.is_network("arbi")).map(|item| item.chain.as_str()));
+  assert!(!bybit_chains.iter().any(|item| item.is_network("Optimism")));
+}
+
+#[tokio::test]
+async fn test_bybit_funding_interval_from_instruments() {
+  let instrument_requests = Arc::new(AtomicUsize::new(0));
+  let counter = instrument_requests.clone();
+  let url = start_http_stub(move |request| {
+    let result = if request.contains("instruments-info") {
+      counter.fetch_add(1, Ordering::SeqCst);
+      if request.contains("category=linear") {
+        r#"{"list": [{"symbol": "BTCUSDT", "baseCoin": "BTC", "quoteCoin": "USDT", "status": "Trading", "contractType": "LinearPerpetual", "deliveryTime": "0", "fundingInterval": 240}]}"#
+      } else {
+        r#"{"list": []}"#
+      }
+    } else {
+      r#"{"list": [{"symbol": "BTCUSDT", "lastPrice": "100000", "bid1Price": "99999", "bid1Size": "1", "ask1Price": "100001", "ask1Size": "1", "fundingRate": "0.0001", "nextFundingTime": "1760000000000"}]}"#
+    };
+    format!(r#"{{"retCode": 0, "retMsg": "OK", "result": {result}}}"#)
+  }).await;
+
+  let bybit = BybitActor { api_key: None, secret_key: None, data_source: "Bybit".to_string() };
+  let config = Exchanges { name: "Bybit".to_string(), trading_fee_rate: dec!(0.0), url, derivatives_url: None };
+
+  let instruments = bybit.fetch_instruments(config.clone()).await.unwrap();
+  let perp = instruments.into_iter().find(|item| item.symbol == "BTCUSDT").unwrap();
+  assert_eq!(Some(4), perp.funding_interval_hours);
+  assert_eq!(2, instrument_requests.load(Ordering::SeqCst));
+
+  let funding = bybit.fetch_funding(perp, config).await.unwrap();
+  assert_eq!(dec!(4), funding.interval_hours);
+  assert_eq!(2, instrument_requests.load(Ordering::SeqCst));
 }
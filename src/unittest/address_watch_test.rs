@@ -0,0 +1,50 @@
+#[cfg(test)]
+use crate::monitors::monitor_address::blocks_to_scan;
+#[cfg(test)]
+use crate::utils::address_watch::{AddressWatchRecord, AddressWatchStore};
+#[cfg(test)]
+use crate::utils::config_struct::AddressWatchConfig;
+
+
+#[test]
+fn test_update_pending() {
+  let mut record = AddressWatchRecord { last_block: 100, nonce: 5, pending_since: None };
+
+  assert_eq!(None, record.update_pending(5, 5, 1000));
+  assert_eq!(Some(0), record.update_pending(5, 6, 1000));
+  assert_eq!(Some(300), record.update_pending(5, 6, 1300));
+  // The stuck transaction was mined, the next pending one starts over
+  assert_eq!(Some(0), record.update_pending(6, 7, 1400));
+  assert_eq!(Some((6, 1400)), record.pending_since);
+  assert_eq!(None, record.update_pending(7, 7, 1500));
+  assert_eq!(None, record.pending_since);
+}
+
+#[test]
+fn test_blocks_to_scan() {
+  assert_eq!(101..=110, blocks_to_scan(100, 110));
+  assert!(blocks_to_scan(110, 110).is_empty());
+  assert_eq!(1501..=2000, blocks_to_scan(100, 2000));
+}
+
+#[test]
+fn test_address_watch_store() {
+  let path = std::env::temp_dir().join("coin_monitor_address_watch.json").to_string_lossy().to_string();
+  std::fs::remove_file(&path).ok();
+
+  let watch_store = AddressWatchStore::load(AddressWatchConfig { path: path.clone() });
+  assert_eq!(None, watch_store.get(1, "0xABC"));
+
+  let record = AddressWatchRecord { last_block: 100, nonce: 5, pending_since: Some((5, 1000)) };
+  watch_store.set(1, "0xABC", record.clone());
+  // Nothing is written until the run flushes the store
+  assert_eq!(None, AddressWatchStore::load(AddressWatchConfig { path: path.clone() }).get(1, "0xabc"));
+  watch_store.flush();
+
+  // The address is case insensitive and the state survives a restart
+  let reloaded = AddressWatchStore::load(AddressWatchConfig { path: path.clone() });
+  assert_eq!(Some(record), reloaded.get(1, "0xabc"));
+  assert_eq!(None, reloaded.get(137, "0xabc"));
+
+  std::fs::remove_file(path).ok();
+}
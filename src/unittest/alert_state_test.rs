@@ -0,0 +1,46 @@
+#[cfg(test)]
+use crate::utils::alert_state::AlertStore;
+#[cfg(test)]
+use crate::utils::config_struct::AlertStateConfig;
+
+
+#[cfg(test)]
+fn build_alert_store(file_name: &str) -> (AlertStore, String) {
+  let path = std::env::temp_dir().join(file_name).to_string_lossy().to_string();
+  std::fs::remove_file(&path).ok();
+  (AlertStore::load(AlertStateConfig { path: path.clone(), cooldown_secs: 3600 }), path)
+}
+
+#[test]
+fn test_alert_cooldown() {
+  let (alert_store, path) = build_alert_store("coin_monitor_alert_cooldown.json");
+
+  assert!(alert_store.should_alert_at("addresses_balances", "1:0xabc", 1000));
+  assert!(!alert_store.should_alert_at("addresses_balances", "1:0xabc", 1000 + 3599));
+  assert!(alert_store.should_alert_at("addresses_balances", "1:0xabc", 1000 + 3600));
+  // Another subject has its own cooldown
+  assert!(alert_store.should_alert_at("addresses_balances", "137:0xabc", 1500));
+
+  // The state survives a restart
+  let reloaded = AlertStore::load(AlertStateConfig { path: path.clone(), cooldown_secs: 3600 });
+  assert!(!reloaded.should_alert_at("addresses_balances", "1:0xabc", 1000 + 3601));
+
+  std::fs::remove_file(path).ok();
+}
+
+#[test]
+fn test_alert_resolve() {
+  let (alert_store, path) = build_alert_store("coin_monitor_alert_resolve.json");
+
+  assert!(!alert_store.resolve("exchange_prices", "BTC/USDT"));
+
+  assert!(alert_store.should_alert_at("exchange_prices", "BTC/USDT", 1000));
+  assert!(alert_store.resolve("exchange_prices", "BTC/USDT"));
+  // Only the first recovery sends a message
+  assert!(!alert_store.resolve("exchange_prices", "BTC/USDT"));
+
+  // A new occurrence is alerted right away, even within the cooldown
+  assert!(alert_store.should_alert_at("exchange_prices", "BTC/USDT", 1100));
+
+  std::fs::remove_file(path).ok();
+}
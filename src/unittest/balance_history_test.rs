@@ -0,0 +1,70 @@
+#[cfg(test)]
+use crate::utils::balance_history::{burn_rate_per_hour, find_balance_drop, hours_left, BalanceHistoryStore, BalanceSample};
+#[cfg(test)]
+use crate::utils::config_struct::BalanceHistoryConfig;
+
+
+#[cfg(test)]
+fn sample(timestamp: i64, balance: f64) -> BalanceSample {
+  BalanceSample { timestamp, balance }
+}
+
+#[test]
+fn test_balance_drop_since_previous_run() {
+  let history = vec!(sample(0, 10.0), sample(300, 8.0));
+
+  let balance_drop = find_balance_drop(&history, sample(600, 6.0), None).unwrap();
+  assert_eq!(sample(300, 8.0), balance_drop.reference);
+  assert_eq!(2.0, balance_drop.amount);
+  assert_eq!(25.0, balance_drop.percent);
+
+  // A top-up is not a drop
+  assert_eq!(None, find_balance_drop(&history, sample(600, 9.0), None));
+  assert_eq!(None, find_balance_drop(&[], sample(600, 6.0), None));
+}
+
+#[test]
+fn test_balance_drop_within_window() {
+  let history = vec!(sample(0, 20.0), sample(300, 10.0), sample(600, 8.0));
+
+  // Compared with the highest balance within the window
+  let balance_drop = find_balance_drop(&history, sample(900, 6.0), Some(700)).unwrap();
+  assert_eq!(sample(300, 10.0), balance_drop.reference);
+  assert_eq!(40.0, balance_drop.percent);
+
+  let balance_drop = find_balance_drop(&history, sample(900, 6.0), Some(900)).unwrap();
+  assert_eq!(14.0, balance_drop.amount);
+
+  // A sample older than the window is not compared with
+  assert_eq!(None, find_balance_drop(&history, sample(2000, 6.0), Some(700)));
+}
+
+#[test]
+fn test_burn_rate() {
+  // Spent 1.0 per half hour, with a top-up in between
+  let history = vec!(sample(0, 10.0), sample(1800, 9.0), sample(3600, 15.0));
+  let burn_rate = burn_rate_per_hour(&history, sample(5400, 14.0)).unwrap();
+  assert!((burn_rate - 4.0 / 3.0).abs() < 1e-9);
+  assert!((hours_left(14.0, burn_rate) - 10.5).abs() < 1e-9);
+
+  assert_eq!(None, burn_rate_per_hour(&[], sample(5400, 14.0)));
+  assert_eq!(None, burn_rate_per_hour(&[sample(0, 10.0)], sample(3600, 12.0)));
+}
+
+#[test]
+fn test_balance_history_retention() {
+  let path = std::env::temp_dir().join("coin_monitor_balance_history.json").to_string_lossy().to_string();
+  std::fs::remove_file(&path).ok();
+
+  let history_store = BalanceHistoryStore::load(BalanceHistoryConfig { path: path.clone(), retention_secs: 3600 });
+  history_store.record("1:0xabc", sample(0, 10.0));
+  history_store.record("1:0xabc", sample(1800, 9.0));
+  history_store.record("1:0xabc", sample(4000, 8.0));
+  history_store.flush();
+
+  let reloaded = BalanceHistoryStore::load(BalanceHistoryConfig { path: path.clone(), retention_secs: 3600 });
+  assert_eq!(vec!(sample(1800, 9.0), sample(4000, 8.0)), reloaded.history("1:0xabc"));
+  assert!(reloaded.history("137:0xabc").is_empty());
+
+  std::fs::remove_file(path).ok();
+}
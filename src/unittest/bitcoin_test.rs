@@ -0,0 +1,71 @@
+#[cfg(test)]
+use crate::unittest::http_stub::start_http_stub;
+#[cfg(test)]
+use crate::blockchain::bitcoin::esplora::EsploraClient;
+#[cfg(test)]
+use crate::blockchain::bitcoin::xpub::{derive_address, is_extended_public_key, parse_xpub};
+
+
+// Account 0 of the "abandon abandon ... about" test mnemonic, from BIP 84
+#[cfg(test)]
+const TEST_ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+/// A local stand-in of Esplora, every address is unused except the ones in `funded`
+#[cfg(test)]
+async fn start_esplora_stub(funded: Vec<(String, u64)>) -> String {
+  start_http_stub(move |request| {
+    let address = request.split_whitespace().nth(1).unwrap().trim_start_matches("/address/").to_string();
+
+    let (funded_sats, tx_count) = funded.iter()
+      .find(|(funded_address, _)| *funded_address == address)
+      .map_or((0, 0), |(_, sats)| (*sats, 1));
+    format!(
+      r#"{{"address":"{address}","chain_stats":{{"funded_txo_sum":{funded_sats},"spent_txo_sum":0,"tx_count":{tx_count}}},"mempool_stats":{{"funded_txo_sum":5000,"spent_txo_sum":0,"tx_count":{tx_count}}}}}"#
+    )
+  }).await
+}
+
+#[test]
+fn test_derive_zpub_address() {
+  assert!(is_extended_public_key(TEST_ZPUB));
+  assert!(!is_extended_public_key("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu"));
+
+  let xpub = parse_xpub(TEST_ZPUB).unwrap();
+  assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", derive_address(&xpub, 0, 0).unwrap());
+  assert_eq!("bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g", derive_address(&xpub, 0, 1).unwrap());
+  assert_eq!("bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el", derive_address(&xpub, 1, 0).unwrap());
+}
+
+#[tokio::test]
+async fn test_fetch_single_address_balance() {
+  let base_url = start_esplora_stub(vec!(("bc1qtest".to_string(), 150_000_000))).await;
+  let esplora = EsploraClient::new(base_url + "/");
+
+  let balance = esplora.fetch_balance("bc1qtest", 20).await.unwrap();
+  assert_eq!(150_000_000, balance.confirmed_sats);
+  assert_eq!(5000, balance.unconfirmed_sats);
+  assert_eq!(1.5, balance.confirmed_btc());
+  assert_eq!(1.50005, balance.total_btc());
+}
+
+#[tokio::test]
+async fn test_fetch_xpub_balance() {
+  let xpub = parse_xpub(TEST_ZPUB).unwrap();
+  let funded = vec!(
+    (derive_address(&xpub, 0, 0).unwrap(), 100_000),
+    // Found because it's within the gap limit of the last used address
+    (derive_address(&xpub, 0, 4).unwrap(), 200_000),
+    // Beyond the gap limit, so never queried
+    (derive_address(&xpub, 0, 12).unwrap(), 400_000),
+    (derive_address(&xpub, 1, 2).unwrap(), 800_000),
+  );
+  let esplora = EsploraClient::new(start_esplora_stub(funded).await);
+
+  let balance = esplora.fetch_balance(TEST_ZPUB, 5).await.unwrap();
+  assert_eq!(1_100_000, balance.confirmed_sats);
+  assert_eq!(15_000, balance.unconfirmed_sats);
+
+  // A gap limit of 0 still checks the first address
+  let balance = esplora.fetch_balance(TEST_ZPUB, 0).await.unwrap();
+  assert_eq!(100_000, balance.confirmed_sats);
+}
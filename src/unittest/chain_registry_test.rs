@@ -0,0 +1,51 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use crate::blockchain::chain_registry::ChainRegistry;
+#[cfg(test)]
+use crate::utils::config_struct::ChainConfig;
+
+
+#[test]
+fn test_built_in_chains() {
+  let chains = ChainRegistry::from_config(&HashMap::new());
+
+  let ethereum = chains.get(1).unwrap();
+  assert_eq!("ETH", ethereum.native_symbol);
+  assert_eq!(18, ethereum.decimals);
+  assert_eq!(Some("https://etherscan.io/address/0xabc".to_string()), ethereum.address_url("0xabc"));
+  assert_eq!(Some("https://etherscan.io/tx/0x123".to_string()), ethereum.tx_url("0x123"));
+  assert_eq!("AVAX", chains.get(43114).unwrap().native_symbol);
+  assert!(chains.get(100).is_none());
+}
+
+#[test]
+fn test_chain_overrides() {
+  let overrides = HashMap::from([
+    ("1".to_string(), ChainConfig {
+      address_url_template: Some("https://eth.blockscout.com/address/{address}".to_string()),
+      ..Default::default()
+    }),
+    ("100".to_string(), ChainConfig {
+      name: Some("Gnosis".to_string()),
+      native_symbol: Some("xDAI".to_string()),
+      block_time_secs: Some(5.0),
+      ..Default::default()
+    }),
+    // Unknown chain without native_symbol is skipped
+    ("250".to_string(), ChainConfig { name: Some("Fantom".to_string()), ..Default::default() }),
+  ]);
+  let chains = ChainRegistry::from_config(&overrides);
+
+  let ethereum = chains.get(1).unwrap();
+  assert_eq!("ETH", ethereum.native_symbol);
+  assert_eq!(Some("https://eth.blockscout.com/address/0xabc".to_string()), ethereum.address_url("0xabc"));
+
+  let gnosis = chains.get(100).unwrap();
+  assert_eq!("Gnosis", gnosis.name);
+  assert_eq!("xDAI", gnosis.native_symbol);
+  assert_eq!(5.0, gnosis.block_time_secs);
+  assert_eq!(None, gnosis.address_url("0xabc"));
+
+  assert!(chains.get(250).is_none());
+}
@@ -0,0 +1,56 @@
+#[cfg(test)]
+use std::sync::Arc;
+#[cfg(test)]
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+#[cfg(test)]
+use tokio::net::TcpListener;
+
+
+/// A local stand-in of an http api, `respond` maps the raw request to the json body of the response.
+/// Returns the base url of the stub.
+#[cfg(test)]
+pub async fn start_http_stub(respond: impl Fn(&str) -> String + Send + Sync + 'static) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let base_url = format!("http://{}", listener.local_addr().unwrap());
+  let respond = Arc::new(respond);
+
+  tokio::spawn(async move {
+    loop {
+      let (mut stream, _) = listener.accept().await.unwrap();
+      let respond = respond.clone();
+      tokio::spawn(async move {
+        let mut request = String::new();
+        let mut buffer = [0u8; 4096];
+        // Read until the headers and the body of `content-length` are complete
+        while !is_complete(&request) {
+          let length = stream.read(&mut buffer).await.unwrap();
+          if length == 0 {
+            break
+          }
+          request.push_str(&String::from_utf8_lossy(&buffer[..length]));
+        }
+
+        let body = respond(&request);
+        let response = format!(
+          "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+          body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+      });
+    }
+  });
+
+  base_url
+}
+
+#[cfg(test)]
+fn is_complete(request: &str) -> bool {
+  let Some((headers, body)) = request.split_once("\r\n\r\n") else {
+    return false
+  };
+  let content_length = headers.lines()
+    .find_map(|line| line.split_once(':').filter(|(name, _)| name.eq_ignore_ascii_case("content-length")))
+    .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+    .unwrap_or(0);
+  body.len() >= content_length
+}
@@ -1 +1,21 @@
-pub mod actor_test;
\ No newline at end of file
+pub mod actor_test;
+pub mod address_watch_test;
+pub mod alert_state_test;
+pub mod balance_history_test;
+pub mod bitcoin_test;
+pub mod chain_registry_test;
+pub mod http_stub;
+pub mod monitor_address_test;
+pub mod monitor_cex_test;
+pub mod monitor_exchange_balance_test;
+pub mod monitor_funding_test;
+pub mod monitor_gas_test;
+pub mod monitor_node_test;
+pub mod node_provider_test;
+pub mod notifier_test;
+pub mod number_utils_test;
+pub mod registry_test;
+pub mod schedule_test;
+pub mod signer_test;
+pub mod solana_test;
+pub mod symbol_test;
\ No newline at end of file
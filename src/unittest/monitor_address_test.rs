@@ -0,0 +1,63 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use serde_json::json;
+#[cfg(test)]
+use crate::monitors::monitor_address::check_evm_address;
+#[cfg(test)]
+use crate::unittest::http_stub::start_http_stub;
+#[cfg(test)]
+use crate::utils::config_struct::{MonitorAddress, NodeProviderConfig};
+
+
+/// A local stand-in of an EVM node holding a 6 decimals "USDC" token, it answers `eth_call` by the function selector
+#[cfg(test)]
+async fn start_erc20_stub() -> String {
+  start_http_stub(|request| {
+    let result = if request.contains("0x313ce567") {
+      // decimals()
+      format!("0x{:064x}", 6)
+    } else if request.contains("0x95d89b41") {
+      // symbol(), the abi encoded "USDC"
+      format!("0x{:064x}{:064x}{:0<64}", 32, 4, "55534443")
+    } else if request.contains("0x70a08231") {
+      // balanceOf(address)
+      format!("0x{:064x}", 1_234_567_890u64)
+    } else {
+      format!("0x{:x}", 100)
+    };
+    json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string()
+  }).await
+}
+
+#[cfg(test)]
+fn token_monitor(symbol: Option<&str>, decimals: Option<u8>) -> MonitorAddress {
+  serde_json::from_value(json!({
+    "name": "Treasury",
+    "address": "0x00000000000000000000000000000000000000aa",
+    "alert_threshold": 100.0,
+    "chain_id": 990101,
+    "token_contract": "0x00000000000000000000000000000000000000bb",
+    "symbol": symbol,
+    "decimals": decimals
+  })).unwrap()
+}
+
+#[tokio::test]
+async fn test_check_erc20_address() {
+  let node_providers = HashMap::from([(
+    "990101".to_string(),
+    NodeProviderConfig { urls_env: vec!("NODE_URL_ERC20_STUB".to_string()), ..Default::default() }
+  )]);
+  std::env::set_var("NODE_URL_ERC20_STUB", start_erc20_stub().await);
+
+  // Decimals and symbol are read from the token contract
+  let (balance, symbol) = check_evm_address(token_monitor(None, None), None, &node_providers).await.unwrap();
+  assert_eq!(1234.56789, balance);
+  assert_eq!("USDC", symbol);
+
+  // The configured ones take precedence
+  let (balance, symbol) = check_evm_address(token_monitor(Some("USDC.e"), Some(8)), None, &node_providers).await.unwrap();
+  assert_eq!(12.3456789, balance);
+  assert_eq!("USDC.e", symbol);
+}
@@ -0,0 +1,69 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use crate::exchanges::{dto::{ArbitrageOpportunity, PriceResult, QuoteNormalization}, symbol::MarketType};
+#[cfg(test)]
+use rust_decimal_macros::dec;
+#[cfg(test)]
+use crate::monitors::monitor_cex::build_spread_alert_message;
+#[cfg(test)]
+use crate::utils::config_struct::Instruments;
+#[cfg(test)]
+use crate::utils::messenger::format_markdown_v2;
+
+
+#[test]
+fn test_build_spread_alert_message() {
+  let inst = Instruments {
+    base_ccy: "USDC".to_string(),
+    target_ccy: "eth".to_string(),
+    withdrawal_chain: "Ethereum".to_string(),
+    market_type: MarketType::Spot,
+    market_type_overrides: HashMap::new(),
+    notional_size: dec!(1000.0),
+    alert_spread_percent: Some(dec!(1.0)),
+    display_precision: None,
+    symbol_overrides: HashMap::new()
+  };
+  let opportunity = ArbitrageOpportunity {
+    buy: PriceResult { data_source: "MEXC".to_string(), instrument: "ETHUSDC".to_string(), trading_fee_rate: dec!(0.1), ..Default::default() },
+    sell: PriceResult { data_source: "OKX".to_string(), instrument: "ETH-USDC-SWAP".to_string(), market_type: MarketType::Perp, ..Default::default() },
+    buy_price: dec!(100.0),
+    sell_price: dec!(103.0),
+    gross_spread: dec!(3.0),
+    gross_percent: dec!(3.0),
+    withdrawal_fee: Some(dec!(0.01)),
+    net_spread: dec!(2.5),
+    net_percent: dec!(2.5),
+    non_actionable_reason: None
+  };
+
+  let message = build_spread_alert_message(&inst, &opportunity, dec!(1.0));
+
+  assert!(message.starts_with("📈 ETH/USDC spread above __1%__ 📈"));
+  assert!(message.contains("\nBuy on *MEXC* (`ETHUSDC`) at 100 with fee 0.1%"));
+  assert!(message.contains("\nSell on *OKX* (`ETH-USDC-SWAP perp`) at 103 with fee 0%"));
+  assert!(message.contains("\nWithdrawal fee: 0.01 ETH"));
+  assert!(message.contains("\nNet spread: __2.5__ (||2.5%||)"));
+
+  let inst = Instruments { display_precision: Some(2), ..inst };
+  let message = build_spread_alert_message(&inst, &opportunity, dec!(1.0));
+  assert!(message.contains("\nBuy on *MEXC* (`ETHUSDC`) at 100.00 with fee 0.1%"));
+  assert!(message.contains("\nNet spread: __2.50__ (||2.50%||)"));
+
+  // A USD pair compared with USDC pairs
+  let normalization = QuoteNormalization {
+    raw_quote_ccy: "USD".to_string(), rate: dec!(1.0005), raw_price: dec!(99.95), raw_buy_price: Some(dec!(99.95)), raw_sell_price: None
+  };
+  let buy = PriceResult { instrument: "ETHUSD".to_string(), normalization: Some(normalization), ..opportunity.buy };
+  let opportunity = ArbitrageOpportunity { buy, ..opportunity };
+  let message = build_spread_alert_message(&inst, &opportunity, dec!(1.0));
+  assert!(message.contains("\nBuy on *MEXC* (`ETHUSD`) at 100.00 (raw 99.95 USD at 1.0005) with fee 0.1%"));
+  assert!(message.contains("\nSell on *OKX* (`ETH-USDC-SWAP perp`) at 103.00 with fee 0%"));
+
+  // The underscore of a MEXC contract stays inside the code span instead of opening an italic
+  let sell = PriceResult { data_source: "MEXC".to_string(), instrument: "ETH_USDC".to_string(), ..opportunity.sell };
+  let opportunity = ArbitrageOpportunity { sell, ..opportunity };
+  let message = format_markdown_v2(&build_spread_alert_message(&inst, &opportunity, dec!(1.0)));
+  assert!(message.contains("\nSell on *MEXC* \\(`ETH_USDC perp`\\) at 103\\.00 with fee 0%"));
+}
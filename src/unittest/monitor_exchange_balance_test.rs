@@ -0,0 +1,105 @@
+#[cfg(test)]
+use rust_decimal_macros::dec;
+#[cfg(test)]
+use crate::exchanges::dto::AssetBalance;
+#[cfg(test)]
+use crate::exchanges::{hashkey, mexc, okx};
+#[cfg(test)]
+use crate::utils::error::HttpError;
+#[cfg(test)]
+use crate::monitors::monitor_exchange_balance::{build_low_balance_message, monitored_amount};
+#[cfg(test)]
+use crate::utils::config_struct::MonitorExchangeBalance;
+
+
+#[test]
+fn test_asset_balance_from_raw() {
+  let balance = AssetBalance::from_raw("OKX", "usdc", "1200.5", "0").unwrap();
+  assert_eq!("USDC", balance.asset);
+  assert_eq!(dec!(1200.5), balance.free);
+  assert_eq!(dec!(0), balance.locked);
+  assert_eq!(dec!(1200.5), balance.total());
+
+  // Never read as a zero balance
+  assert!(matches!(AssetBalance::from_raw("OKX", "USDC", "", "0"), Err(HttpError::ResponseDataError)));
+  assert!(matches!(AssetBalance::from_raw("OKX", "USDC", "1200.5", "n/a"), Err(HttpError::ResponseDataError)));
+}
+
+#[test]
+fn test_okx_balance_payload() {
+  let data: okx::dto::AccountBalanceData = serde_json::from_str(r#"{
+    "totalEq": "1300.5",
+    "details": [
+      {"ccy": "USDC", "availBal": "1200.5", "cashBal": "1300.5", "frozenBal": "100"},
+      {"ccy": "ETH", "availBal": "", "cashBal": "2.5", "frozenBal": "0.5"}
+    ]
+  }"#).unwrap();
+  let balances: Vec<AssetBalance> = data.details.iter()
+    .map(|detail| okx::actor::to_asset_balance("OKX", detail).unwrap())
+    .collect();
+
+  assert_eq!(dec!(1200.5), balances[0].free);
+  assert_eq!(dec!(100), balances[0].locked);
+  // Without availBal, the cash balance minus the frozen one
+  assert_eq!("ETH", balances[1].asset);
+  assert_eq!(dec!(2.0), balances[1].free);
+  assert_eq!(dec!(0.5), balances[1].locked);
+}
+
+#[test]
+fn test_mexc_balance_payload() {
+  let data: mexc::dto::AccountInfo = serde_json::from_str(r#"{
+    "canTrade": true,
+    "balances": [{"asset": "USDT", "free": "100.25", "locked": "0"}]
+  }"#).unwrap();
+  let balance = &data.balances[0];
+  let balance = AssetBalance::from_raw("MEXC", &balance.asset, &balance.free, &balance.locked).unwrap();
+
+  assert_eq!("USDT", balance.asset);
+  assert_eq!(dec!(100.25), balance.free);
+  assert_eq!(dec!(0), balance.locked);
+}
+
+#[test]
+fn test_hashkey_balance_payload() {
+  let data: hashkey::dto::AccountInfo = serde_json::from_str(r#"{
+    "balances": [{"asset": "BTC", "assetId": "BTC", "assetName": "BTC", "total": "0.3", "free": "0.25", "locked": "0.05"}],
+    "userId": "1"
+  }"#).unwrap();
+  let balance = &data.balances[0];
+  let balance = AssetBalance::from_raw("HashKey", &balance.asset, &balance.free, &balance.locked).unwrap();
+
+  assert_eq!(dec!(0.25), balance.free);
+  assert_eq!(dec!(0.3), balance.total());
+}
+
+#[test]
+fn test_monitored_amount() {
+  let balances = vec!(
+    AssetBalance::from_raw("MEXC", "ETH", "0.3", "0.4").unwrap(),
+    AssetBalance::from_raw("MEXC", "USDT", "100", "0").unwrap()
+  );
+  let monitor = MonitorExchangeBalance {
+    exchange: "mexc".to_string(), asset: "eth".to_string(), alert_threshold: dec!(0.5), include_locked: false
+  };
+  assert_eq!(dec!(0.3), monitored_amount(&monitor, &balances));
+  assert_eq!("mexc:ETH", monitor.alert_subject());
+
+  let monitor = MonitorExchangeBalance { include_locked: true, ..monitor };
+  assert_eq!(dec!(0.7), monitored_amount(&monitor, &balances));
+
+  // Assets without balance are left out by the exchanges
+  let monitor = MonitorExchangeBalance { asset: "BTC".to_string(), ..monitor };
+  assert_eq!(dec!(0), monitored_amount(&monitor, &balances));
+}
+
+#[test]
+fn test_build_low_balance_message() {
+  let monitor = MonitorExchangeBalance {
+    exchange: "okx".to_string(), asset: "usdc".to_string(), alert_threshold: dec!(1000.0), include_locked: false
+  };
+  let message = build_low_balance_message(&monitor, "OKX", dec!(250.50));
+
+  assert!(message.starts_with("‼️ Exchange balance below __1000__ USDC alert ‼️"));
+  assert!(message.contains("\nThe *OKX* account \nfree USDC balance is ||__250.5__|| 💸"));
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+use rust_decimal_macros::dec;
+#[cfg(test)]
+use crate::exchanges::dto::FundingInfo;
+#[cfg(test)]
+use crate::monitors::monitor_funding::{build_funding_alert_message, find_funding_divergence};
+
+
+#[test]
+fn test_annualized_funding_and_basis() {
+  let funding = FundingInfo {
+    data_source: "OKX".to_string(),
+    instrument: "BTC-USDT-SWAP".to_string(),
+    funding_rate: dec!(0.0001),
+    interval_hours: dec!(8),
+    mark_price: Some(dec!(100.5)),
+    index_price: Some(dec!(100)),
+    ..Default::default()
+  };
+  // 0.01% three times a day
+  assert_eq!(dec!(10.95), funding.annualized_percent());
+  assert_eq!(Some(dec!(0.5)), funding.basis_percent());
+
+  let hourly = FundingInfo { interval_hours: dec!(1), index_price: None, ..funding };
+  assert_eq!(dec!(87.6), hourly.annualized_percent());
+  assert_eq!(None, hourly.basis_percent());
+}
+
+#[test]
+fn test_find_funding_divergence() {
+  let funding = |data_source: &str, instrument: &str, funding_rate, interval_hours| FundingInfo {
+    data_source: data_source.to_string(), instrument: instrument.to_string(), funding_rate, interval_hours,
+    mark_price: Some(dec!(101)), index_price: Some(dec!(100)), ..Default::default()
+  };
+  let fundings = vec!(
+    funding("OKX", "BTC-USDT-SWAP", dec!(0.0001), dec!(8)),
+    funding("MEXC", "BTC_USDT", dec!(-0.0001), dec!(8)),
+    // Higher per year than OKX even with a lower rate
+    funding("Bybit", "BTCUSDT", dec!(0.00005), dec!(1))
+  );
+
+  assert!(find_funding_divergence(&fundings[..1]).is_none());
+
+  let divergence = find_funding_divergence(&fundings).unwrap();
+  assert_eq!("Bybit", divergence.highest.data_source);
+  assert_eq!("MEXC", divergence.lowest.data_source);
+  assert_eq!(dec!(54.75), divergence.divergence_percent);
+
+  let message = build_funding_alert_message("BTC/USDT", &divergence, dec!(20.0));
+  assert!(message.starts_with("💸 BTC/USDT funding gap above __20%__ a year 💸"));
+  assert!(message.contains("\nHighest: *Bybit* (`BTCUSDT`) at 0.0050% every 1h, __43.80%__ a year, basis 1.0000%"));
+  assert!(message.contains("\nLowest: *MEXC* (`BTC_USDT`) at -0.0100% every 8h, __-10.95%__ a year"));
+  assert!(message.contains("\nGap: __54.75%__ a year, short on Bybit and long on MEXC"));
+}
@@ -0,0 +1,30 @@
+#[cfg(test)]
+use crate::monitors::monitor_gas::{check_gas_price, transfer_cost_usd, GasLevel, GasPrice};
+#[cfg(test)]
+use crate::utils::config_struct::MonitorGasPrice;
+#[cfg(test)]
+use rust_decimal_macros::dec;
+
+
+#[test]
+fn test_check_gas_price() {
+  let monitor_gas_price = MonitorGasPrice { chain_id: 1, below_gwei: Some(5.0), above_gwei: Some(100.0) };
+
+  assert_eq!(Some(GasLevel::Below(5.0)), check_gas_price(&monitor_gas_price, 4.2));
+  assert_eq!(Some(GasLevel::Above(100.0)), check_gas_price(&monitor_gas_price, 120.0));
+  assert_eq!(None, check_gas_price(&monitor_gas_price, 30.0));
+  assert_eq!(None, check_gas_price(&monitor_gas_price, 5.0));
+
+  let only_below = MonitorGasPrice { above_gwei: None, ..monitor_gas_price };
+  assert_eq!(None, check_gas_price(&only_below, 500.0));
+}
+
+#[test]
+fn test_transfer_cost_usd() {
+  let gas_price = GasPrice { base_fee_gwei: 18.0, priority_fee_gwei: 2.0 };
+  assert_eq!(20.0, gas_price.total_gwei());
+
+  // 21000 gas * 20 gwei = 0.00042 ETH
+  let cost = transfer_cost_usd(gas_price.total_gwei(), dec!(2500));
+  assert!((cost - 1.05).abs() < 1e-9);
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+use tokio::time::Duration;
+#[cfg(test)]
+use crate::blockchain::chain_registry::ChainRegistry;
+#[cfg(test)]
+use crate::monitors::monitor_node::{check_node_status, default_max_block_age_secs, NodeStatus};
+
+
+#[cfg(test)]
+fn build_node_status() -> NodeStatus {
+  NodeStatus {
+    chain_id: 1,
+    block_number: 19_000_000,
+    block_timestamp: 1_700_000_000,
+    syncing: false,
+    latency: Duration::from_millis(200)
+  }
+}
+
+#[test]
+fn test_healthy_node() {
+  let status = build_node_status();
+  assert!(check_node_status(1, &status, 240, Some(1000), 1_700_000_030).is_empty());
+}
+
+#[test]
+fn test_unhealthy_node() {
+  let status = NodeStatus { chain_id: 137, syncing: true, latency: Duration::from_millis(1500), ..build_node_status() };
+  let problems = check_node_status(1, &status, 240, Some(1000), 1_700_000_300);
+
+  assert_eq!(
+    vec!(
+      "chain id is 137, expected 1",
+      "latest block 19000000 is 300 seconds old",
+      "node is still syncing",
+      "latency 1500ms is above 1000ms"
+    ),
+    problems
+  );
+  // Latency is only checked when configured
+  assert_eq!(3, check_node_status(1, &status, 240, None, 1_700_000_300).len());
+}
+
+#[test]
+fn test_default_max_block_age() {
+  let chains = ChainRegistry::from_config(&Default::default());
+  assert_eq!(240, default_max_block_age_secs(chains.get(1)));
+  assert_eq!(60, default_max_block_age_secs(chains.get(42161)));
+  assert_eq!(240, default_max_block_age_secs(None));
+}
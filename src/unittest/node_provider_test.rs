@@ -0,0 +1,40 @@
+#[cfg(test)]
+use crate::blockchain::node_provider::{build_ethers_provider, find_quorum};
+#[cfg(test)]
+use crate::utils::config_struct::NodeProviderConfig;
+#[cfg(test)]
+use crate::utils::error::NodeError;
+
+
+#[test]
+fn test_find_quorum() {
+  assert_eq!(Some(10), find_quorum(vec!(Some(10), Some(10), Some(11)), 3));
+  // A failed endpoint doesn't count as agreeing
+  assert_eq!(Some(10), find_quorum(vec!(Some(10), Some(10), None), 3));
+  assert_eq!(None, find_quorum(vec!(Some(10), None, None), 3));
+  assert_eq!(None, find_quorum(vec!(Some(10), Some(11), Some(12)), 3));
+  assert_eq!(None, find_quorum(vec!(Some(10), Some(10), Some(11), Some(11)), 4));
+  assert_eq!(Some(10), find_quorum(vec!(Some(10)), 1));
+}
+
+#[test]
+fn test_build_ethers_provider_with_failover_urls() {
+  std::env::set_var("NODE_URL_990001", "http://localhost:8545");
+  std::env::set_var("NODE_URL_990001_1", "http://localhost:8546");
+  std::env::set_var("NODE_URL_990001_2", "http://localhost:8547");
+  // It stops at the first missing index
+  std::env::set_var("NODE_URL_990001_4", "http://localhost:8548");
+
+  let node = build_ethers_provider(990001, None).unwrap();
+  let names: Vec<String> = node.endpoints.iter().map(|endpoint| endpoint.name.clone()).collect();
+  assert_eq!(vec!("NODE_URL_990001", "NODE_URL_990001_1", "NODE_URL_990001_2"), names);
+  assert!(!node.quorum);
+
+  let config = NodeProviderConfig { urls_env: vec!("NODE_URL_990001_2".to_string()), timeout_secs: 3, quorum: true, ..Default::default() };
+  let node = build_ethers_provider(990001, Some(&config)).unwrap();
+  assert_eq!(1, node.endpoints.len());
+  assert_eq!(3, node.timeout.as_secs());
+  assert!(node.quorum);
+
+  assert!(matches!(build_ethers_provider(990002, None), Err(NodeError::NoEndpoint(990002))));
+}
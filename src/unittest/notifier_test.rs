@@ -0,0 +1,84 @@
+#[cfg(test)]
+use crate::notifiers::{
+  discord::{to_discord_markdown, DiscordNotifier},
+  notifier::{MockNotifier, Notifier},
+  registry::NotifierRegistry,
+  slack::to_slack_mrkdwn,
+  webhook::to_plain_text
+};
+#[cfg(test)]
+use crate::utils::{
+  config_struct::NotifierChannel,
+  error::HttpError,
+  messenger::{escape_markdown, format_markdown_v2, send_one_message}
+};
+
+
+#[tokio::test]
+async fn test_send_message_to_all_channels() {
+  let mut failed_notifier = MockNotifier::new();
+  failed_notifier.expect_name().return_const("Failed".to_string());
+  failed_notifier.expect_send_message().times(1).returning(|_| Err(HttpError::ResponseError));
+
+  let mut notifier = MockNotifier::new();
+  notifier.expect_send_message()
+    .withf(|message| message == "Balance below __3__")
+    .times(1)
+    .returning(|_| Ok(()));
+
+  let mut registry = NotifierRegistry::default();
+  registry.register(Box::new(failed_notifier));
+  registry.register(Box::new(notifier));
+
+  registry.send_message("Balance below __3__".to_string()).await;
+}
+
+#[test]
+fn test_skip_channel_without_webhook_url() {
+  let registry = NotifierRegistry::from_config(vec!(
+    NotifierChannel { channel: "discord".to_string(), chat_id_env: None, token_env: None, webhook_url_env: None },
+    NotifierChannel { channel: "email".to_string(), chat_id_env: None, token_env: None, webhook_url_env: None }
+  ));
+
+  assert!(registry.notifiers.is_empty());
+}
+
+#[tokio::test]
+async fn test_webhook_without_env_var() {
+  let notifier = DiscordNotifier::new("NO_SUCH_DISCORD_WEBHOOK_URL".to_string());
+  let result = notifier.send_message("Balance is 2.5 ETH".to_string()).await;
+
+  assert!(matches!(result, Err(HttpError::MissingApiKey(env)) if env == "NO_SUCH_DISCORD_WEBHOOK_URL"));
+}
+
+#[test]
+fn test_message_formats() {
+  let message = "Balance is ||__2.5__|| *ETH*".to_string();
+
+  assert_eq!("Balance is ||__2.5__|| **ETH**", to_discord_markdown(message.clone()));
+  assert_eq!("Balance is _2.5_ *ETH*", to_slack_mrkdwn(message.clone()));
+  assert_eq!("Balance is 2.5 ETH", to_plain_text(message));
+}
+
+#[test]
+fn test_format_markdown_v2() {
+  let message = format!(
+    "‼️ Balance below __3.5__ alert ‼️ \nThe address \n_0xabc_ ({}) \nbalance is ||__1.25__|| ETH (-64.3%) \n*OKX* (`BTC-USDT_SWAP.1`) at 1+1=2! #1 [a] {{b}} > c | d",
+    escape_markdown("My_Wallet *1*")
+  );
+
+  assert_eq!(
+    "‼️ Balance below __3\\.5__ alert ‼️ \nThe address \n_0xabc_ \\(My\\_Wallet \\*1\\*\\) \nbalance is ||__1\\.25__|| ETH \\(\\-64\\.3%\\) \n*OKX* \\(`BTC-USDT_SWAP.1`\\) at 1\\+1\\=2\\! \\#1 \\[a\\] \\{b\\} \\> c \\| d",
+    format_markdown_v2(&message)
+  );
+  assert_eq!("Name is My_Wallet *1*", to_plain_text(format!("Name is {}", escape_markdown("My_Wallet *1*"))));
+}
+
+#[tokio::test]
+async fn test_telegram_without_env_vars() {
+  let result = send_one_message(
+    "Balance is 2.5 ETH".to_string(), Some("NO_SUCH_TELEGRAM_CHAT_ID".to_string()), Some("NO_SUCH_TELEGRAM_TOKEN".to_string())
+  ).await;
+
+  assert!(matches!(result, Err(HttpError::MissingApiKey(env_vars)) if env_vars == "NO_SUCH_TELEGRAM_CHAT_ID and NO_SUCH_TELEGRAM_TOKEN"));
+}
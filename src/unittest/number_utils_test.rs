@@ -0,0 +1,237 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use rust_decimal::Decimal;
+#[cfg(test)]
+use rust_decimal_macros::dec;
+#[cfg(test)]
+use crate::exchanges::{dto::{NetworkInfo, OrderBook, OrderBookLevel, PriceResult}, symbol::MarketType};
+#[cfg(test)]
+use crate::utils::number_utils::{
+  calculate_effective_price, calculate_price_with_trading_fee, find_arbitrage_opportunity, format_decimal, median,
+  normalize_quotes, parse_decimal, quote_ccys_to_normalize, sort_price_result
+};
+#[cfg(test)]
+use crate::utils::error::HttpError;
+
+
+#[test]
+fn test_calculate_effective_price() {
+  let order_book = OrderBook::from_raw_levels(
+    vec!(vec!("99".to_string(), "1".to_string()), vec!("98".to_string(), "2".to_string())),
+    vec!(vec!("100".to_string(), "1".to_string()), vec!("110".to_string(), "5".to_string())),
+    dec!(1.0)
+  ).unwrap();
+
+  assert_eq!(OrderBookLevel { price: dec!(100.0), size: dec!(1.0) }, *order_book.asks.first().unwrap());
+
+  // Top of book only
+  assert_eq!(Some(dec!(100.0)), calculate_effective_price(&order_book.asks, dec!(50.0)));
+  // 100 at 100 and 110 at 110 => 210 / 2
+  assert_eq!(Some(dec!(105.0)), calculate_effective_price(&order_book.asks, dec!(210.0)));
+  // 99 at 99 and 98 at 98 => 197 / 2
+  assert_eq!(Some(dec!(98.5)), calculate_effective_price(&order_book.bids, dec!(197.0)));
+  // Not deep enough
+  assert_eq!(None, calculate_effective_price(&order_book.bids, dec!(1000.0)));
+}
+
+#[test]
+fn test_order_book_contract_size() {
+  let order_book = OrderBook::from_raw_levels(
+    vec!(vec!("2000".to_string(), "10".to_string(), "0".to_string(), "3".to_string())),
+    vec!(),
+    dec!(0.5)
+  ).unwrap();
+
+  assert_eq!(dec!(5.0), order_book.bids.first().unwrap().size);
+  assert!(OrderBook::from_raw_levels(vec!(vec!("abc".to_string())), vec!(), dec!(1.0)).is_err());
+}
+
+#[test]
+fn test_find_arbitrage_opportunity() {
+  let price_result = |data_source: &str, buy_price: Option<Decimal>, sell_price: Option<Decimal>, trading_fee_rate: Decimal| PriceResult {
+    data_source: data_source.to_string(), instrument: "ETHUSDC".to_string(), buy_price, sell_price, trading_fee_rate, ..Default::default()
+  };
+  let price_results = vec!(
+    price_result("OKX", Some(dec!(101.0)), Some(dec!(100.0)), dec!(0.0)),
+    price_result("MEXC", Some(dec!(100.0)), Some(dec!(98.0)), dec!(2.0)),
+    price_result("HashKey", None, Some(dec!(104.0)), dec!(0.0))
+  );
+
+  let opportunity = find_arbitrage_opportunity(&price_results, &HashMap::new(), dec!(1000.0)).unwrap();
+  // MEXC has the cheapest ask but its fee makes OKX the better place to buy
+  assert_eq!("OKX", opportunity.buy.data_source);
+  assert_eq!("HashKey", opportunity.sell.data_source);
+  assert_eq!(dec!(3.0), opportunity.gross_spread);
+  assert_eq!(dec!(3.0), opportunity.net_spread);
+  // Without network info the coins may not be movable
+  assert_eq!(Some("Withdrawal network on OKX is unknown".to_string()), opportunity.non_actionable_reason);
+
+  let opportunity = find_arbitrage_opportunity(&price_results[..2], &HashMap::new(), dec!(1000.0)).unwrap();
+  assert_eq!("MEXC", opportunity.buy.data_source);
+  assert_eq!("OKX", opportunity.sell.data_source);
+  assert_eq!(dec!(0.0), opportunity.gross_spread);
+  assert_eq!(dec!(-2.0), opportunity.net_spread);
+
+  assert!(find_arbitrage_opportunity(&price_results[..1], &HashMap::new(), dec!(1000.0)).is_none());
+}
+
+#[test]
+fn test_arbitrage_opportunity_with_network_info() {
+  let price_result = |data_source: &str, buy_price: Option<Decimal>, sell_price: Option<Decimal>| PriceResult {
+    data_source: data_source.to_string(), instrument: "ETHUSDC".to_string(), buy_price, sell_price, ..Default::default()
+  };
+  let network_info = |data_source: &str, withdraw_enable: bool, deposit_enable: bool, withdraw_fee: Decimal| NetworkInfo {
+    data_source: data_source.to_string(), chain: "ETH-ERC20".to_string(), withdraw_enable, deposit_enable, withdraw_fee
+  };
+  let price_results = vec!(
+    price_result("OKX", Some(dec!(100.0)), Some(dec!(99.0))),
+    price_result("MEXC", Some(dec!(101.0)), Some(dec!(110.0))),
+    price_result("Binance", Some(dec!(102.0)), Some(dec!(103.0)))
+  );
+
+  let open_network_infos = |okx_network_info: NetworkInfo| HashMap::from([
+    ("OKX".to_string(), okx_network_info),
+    ("MEXC".to_string(), network_info("MEXC", true, true, dec!(0.0))),
+    ("Binance".to_string(), network_info("Binance", true, true, dec!(0.0)))
+  ]);
+
+  // Buying 10 ETH with 1000, withdrawing 1 ETH costs 110 / 10 per ETH
+  let network_infos = open_network_infos(network_info("OKX", true, true, dec!(1.0)));
+  let opportunity = find_arbitrage_opportunity(&price_results[..2], &network_infos, dec!(1000.0)).unwrap();
+  assert_eq!("OKX", opportunity.buy.data_source);
+  assert_eq!("MEXC", opportunity.sell.data_source);
+  assert_eq!(Some(dec!(1.0)), opportunity.withdrawal_fee);
+  assert_eq!(dec!(10.0), opportunity.gross_spread);
+  assert_eq!(dec!(-1.0), opportunity.net_spread);
+  assert!(opportunity.is_actionable());
+
+  // OKX withdrawal is closed, so buying on Binance is the best actionable one even it is worse
+  let network_infos = open_network_infos(network_info("OKX", false, true, dec!(0.0)));
+  let opportunity = find_arbitrage_opportunity(&price_results, &network_infos, dec!(1000.0)).unwrap();
+  assert_eq!("Binance", opportunity.buy.data_source);
+  assert_eq!("MEXC", opportunity.sell.data_source);
+  assert!(opportunity.is_actionable());
+
+  // Nothing can be moved in or out of OKX
+  let network_infos = open_network_infos(network_info("OKX", false, false, dec!(0.0)));
+  let opportunity = find_arbitrage_opportunity(&price_results[..2], &network_infos, dec!(1000.0)).unwrap();
+  assert_eq!("OKX", opportunity.buy.data_source);
+  assert!(!opportunity.is_actionable());
+  assert_eq!(Some("Withdrawal of ETH-ERC20 on OKX is disabled".to_string()), opportunity.non_actionable_reason);
+
+  // MEXC doesn't tell if deposits are open, so buying on Binance and selling on MEXC can't be alerted
+  let network_infos = HashMap::from([("Binance".to_string(), network_info("Binance", true, true, dec!(0.0)))]);
+  let opportunity = find_arbitrage_opportunity(&price_results[1..], &network_infos, dec!(1000.0)).unwrap();
+  assert_eq!("Binance", opportunity.buy.data_source);
+  assert_eq!("MEXC", opportunity.sell.data_source);
+  assert!(!opportunity.is_actionable());
+  assert_eq!(Some("Deposit network on MEXC is unknown".to_string()), opportunity.non_actionable_reason);
+}
+
+#[test]
+fn test_parse_and_format_decimal() {
+  assert_eq!(Some(dec!(0.1)), parse_decimal("0.1"));
+  assert_eq!(Some(dec!(0.00000001)), parse_decimal("1e-8"));
+  assert_eq!(None, parse_decimal("NaN"));
+  assert_eq!(None, parse_decimal(""));
+
+  assert_eq!("1.5", format_decimal(dec!(1.50000), None));
+  assert_eq!("1.50", format_decimal(dec!(1.5), Some(2)));
+  assert_eq!("0.1235", format_decimal(dec!(0.123456), Some(4)));
+}
+
+#[test]
+fn test_exact_price_arithmetic() {
+  // 0.1 + 0.2 is exactly 0.3, and a 0.1% fee on 0.3 is exactly 0.3003
+  let price = calculate_price_with_trading_fee("OKX".to_string(), (dec!(0.1) + dec!(0.2)).to_string(), dec!(0.1)).unwrap();
+  assert_eq!(dec!(0.3003), price);
+  // An unparsable price is an error of the response instead of a panic
+  assert!(matches!(
+    calculate_price_with_trading_fee("OKX".to_string(), "".to_string(), dec!(0.1)),
+    Err(HttpError::ResponseDataError)
+  ));
+
+  let price_result = |data_source: &str, price: Decimal| PriceResult { data_source: data_source.to_string(), price, ..Default::default() };
+  let sorted_results = sort_price_result(vec!(
+    Ok(price_result("OKX", dec!(0.30000001))),
+    Ok(price_result("MEXC", dec!(0.3))),
+    Ok(price_result("Binance", dec!(0.30000002)))
+  ));
+  let data_sources: Vec<String> = sorted_results.into_iter().map(|result| result.data_source).collect();
+  assert_eq!(vec!("Binance", "OKX", "MEXC"), data_sources);
+}
+
+#[test]
+fn test_arbitrage_opportunity_of_derivatives() {
+  let price_result = |data_source: &str, market_type: MarketType, buy_price: Decimal, sell_price: Decimal| PriceResult {
+    data_source: data_source.to_string(), instrument: "ETH_USDT".to_string(), market_type,
+    buy_price: Some(buy_price), sell_price: Some(sell_price), ..Default::default()
+  };
+  let network_infos = HashMap::from([("OKX".to_string(), NetworkInfo {
+    data_source: "OKX".to_string(), chain: "ETH-ERC20".to_string(), withdraw_enable: false, deposit_enable: true, withdraw_fee: dec!(1)
+  })]);
+
+  // Buying spot on OKX and selling the perpetual on MEXC doesn't need a withdrawal
+  let price_results = vec!(
+    price_result("OKX", MarketType::Spot, dec!(100), dec!(99)),
+    price_result("MEXC", MarketType::Perp, dec!(101), dec!(102))
+  );
+  let opportunity = find_arbitrage_opportunity(&price_results, &network_infos, dec!(1000)).unwrap();
+  assert_eq!("OKX", opportunity.buy.data_source);
+  assert_eq!(None, opportunity.withdrawal_fee);
+  assert_eq!(dec!(2), opportunity.net_spread);
+  assert!(opportunity.is_actionable());
+  assert_eq!("ETH_USDT perp", opportunity.sell.display_instrument());
+}
+
+
+#[test]
+fn test_median() {
+  assert_eq!(None, median(vec!()));
+  assert_eq!(Some(dec!(1.0002)), median(vec!(dec!(1.0002))));
+  assert_eq!(Some(dec!(0.9999)), median(vec!(dec!(1.2), dec!(0.9998), dec!(0.9999))));
+  assert_eq!(Some(dec!(1.0001)), median(vec!(dec!(1.0002), dec!(0.9), dec!(1.0000), dec!(1.1))));
+}
+
+#[test]
+fn test_normalize_quotes() {
+  let price_result = |data_source: &str, quote_ccy: &str, price: Decimal| PriceResult {
+    data_source: data_source.to_string(), quote_ccy: quote_ccy.to_string(), price,
+    best_bid: Some(OrderBookLevel { price, size: dec!(1) }),
+    buy_price: Some(price), sell_price: None, ..Default::default()
+  };
+  let all_results = vec!(
+    Ok(price_result("OKX", "USDC", dec!(3000))),
+    Ok(price_result("HashKey", "USD", dec!(3000))),
+    Ok(price_result("Binance", "USDT", dec!(3000))),
+    Ok(price_result("Kraken", "EUR", dec!(2800))),
+    Err(HttpError::Unsupported)
+  );
+
+  assert!(quote_ccys_to_normalize(&all_results[..1], "usdc").is_empty());
+  assert_eq!(vec!("EUR", "USDC", "USDT"), quote_ccys_to_normalize(&all_results, "usdc"));
+
+  let usd_rates = HashMap::from([("USDC".to_string(), dec!(0.9995)), ("USDT".to_string(), dec!(1.0005))]);
+  let normalized = normalize_quotes(all_results, "usdc", &usd_rates);
+
+  let okx = normalized[0].as_ref().unwrap();
+  assert_eq!(dec!(3000), okx.price);
+  assert!(okx.normalization.is_none());
+
+  // 1 USD buys 1 / 0.9995 USDC
+  let hashkey = normalized[1].as_ref().unwrap();
+  assert_eq!("USDC", hashkey.quote_ccy);
+  assert_eq!("3001.5008", format_decimal(hashkey.price, Some(4)));
+  assert_eq!(hashkey.buy_price, hashkey.best_bid.as_ref().map(|level| level.price));
+  assert_eq!(None, hashkey.sell_price);
+  let normalization = hashkey.normalization.as_ref().unwrap();
+  assert_eq!(("USD", dec!(3000), Some(dec!(3000))), (normalization.raw_quote_ccy.as_str(), normalization.raw_price, normalization.raw_buy_price));
+
+  let binance = normalized[2].as_ref().unwrap();
+  assert_eq!("3003.0015", format_decimal(binance.price, Some(4)));
+  assert_eq!("USDT", binance.normalization.as_ref().unwrap().raw_quote_ccy);
+
+  assert!(matches!(&normalized[3], Err(HttpError::NoQuoteRate(from, to)) if from == "EUR" && to == "USDC"));
+  assert!(matches!(&normalized[4], Err(HttpError::Unsupported)));
+}
\ No newline at end of file
@@ -0,0 +1,215 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use crate::exchanges::{
+  dto::{AssetBalance, FundingInfo, OrderBookLevel, PriceResult},
+  exchange::MockExchange,
+  registry::ExchangeRegistry,
+  symbol::{InstrumentInfo, MarketType}
+};
+#[cfg(test)]
+use crate::utils::{
+  config_struct::{Exchanges, FundingInstrument, Instruments},
+  error::HttpError,
+  number_utils::sort_price_result
+};
+#[cfg(test)]
+use rust_decimal_macros::dec;
+
+
+#[tokio::test]
+async fn test_registry_fetch_prices() {
+  let inst = Instruments {
+    base_ccy: "USDC".to_string(),
+    target_ccy: "ETH".to_string(),
+    withdrawal_chain: "Ethereum".to_string(),
+    market_type: MarketType::Spot,
+    market_type_overrides: HashMap::new(),
+    notional_size: dec!(1000.0),
+    alert_spread_percent: None,
+    display_precision: None,
+    symbol_overrides: HashMap::new()
+  };
+
+  let url = "https://some-url.org".to_string();
+
+  let mut mock_okx = MockExchange::new();
+  mock_okx.expect_data_source().return_const("OKX".to_string());
+  mock_okx.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(
+    InstrumentInfo::new("ETH-USDC", "ETH", "USDC", MarketType::Spot),
+    InstrumentInfo::new("ETH-USDC-SWAP", "ETH", "USDC", MarketType::Perp)
+  )));
+  mock_okx.expect_fetch_price().times(1).returning(|_, symbol, _| Ok(
+    PriceResult { data_source: "OKX".to_string(), instrument: symbol.symbol, price: dec!(2.5), ..Default::default() }
+  ));
+
+  let mut mock_mexc = MockExchange::new();
+  mock_mexc.expect_data_source().return_const("MEXC".to_string());
+  mock_mexc.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(InstrumentInfo::new("ETHUSDC", "ETH", "USDC", MarketType::Spot))));
+  mock_mexc.expect_fetch_price().times(1).returning(|_, _, _| Err(HttpError::ResponseError));
+
+  let mut mock_new_venue = MockExchange::new();
+  mock_new_venue.expect_data_source().return_const("NewVenue".to_string());
+  mock_new_venue.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(InstrumentInfo::new("ETHUSDC", "ETH", "USDC", MarketType::Spot))));
+  mock_new_venue.expect_fetch_price().times(1).returning(|_, symbol, exchange_config| Ok(
+    PriceResult { data_source: exchange_config.name, instrument: symbol.symbol, price: dec!(2.6), ..Default::default() }
+  ));
+
+  // Only lists the USD pair, which stands in for the USDC pair
+  let mut mock_usd_venue = MockExchange::new();
+  mock_usd_venue.expect_data_source().return_const("UsdVenue".to_string());
+  mock_usd_venue.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(InstrumentInfo::new("ETHUSD", "ETH", "USD", MarketType::Spot))));
+  mock_usd_venue.expect_fetch_price().times(1).returning(|_, symbol, exchange_config| Ok(
+    PriceResult { data_source: exchange_config.name, instrument: symbol.symbol, quote_ccy: symbol.base_ccy, price: dec!(2.55), ..Default::default() }
+  ));
+
+  // Lists neither the pair nor an equivalent one, so it's never priced
+  let mut mock_eur_venue = MockExchange::new();
+  mock_eur_venue.expect_data_source().return_const("EurVenue".to_string());
+  mock_eur_venue.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(InstrumentInfo::new("ETHEUR", "ETH", "EUR", MarketType::Spot))));
+  mock_eur_venue.expect_fetch_price().never();
+
+  let mut registry = ExchangeRegistry::default();
+  registry.register(Exchanges { name: "OKX".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }, Box::new(mock_okx));
+  registry.register(Exchanges { name: "MEXC".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }, Box::new(mock_mexc));
+  registry.register(Exchanges { name: "NewVenue".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }, Box::new(mock_new_venue));
+  registry.register(Exchanges { name: "UsdVenue".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }, Box::new(mock_usd_venue));
+  registry.register(Exchanges { name: "EurVenue".to_string(), trading_fee_rate: dec!(0.0), url: url.clone(), derivatives_url: None }, Box::new(mock_eur_venue));
+  registry.load_catalogs().await;
+
+  let all_results = registry.fetch_prices(inst).await;
+  assert_eq!(5, all_results.len());
+  assert!(all_results.get(1).unwrap().is_err());
+  let usd_result = all_results.get(3).unwrap().as_ref().unwrap();
+  assert_eq!("ETHUSD", usd_result.instrument);
+  assert_eq!("USD", usd_result.quote_ccy);
+  match all_results.get(4).unwrap() {
+    Err(HttpError::UnlistedInstrument(pair)) => assert_eq!("ETH/USDC spot", pair),
+    other => panic!("Unexpected result: {:?}", other)
+  }
+
+  let sorted_results = sort_price_result(all_results);
+  assert_eq!(3, sorted_results.len());
+  assert_eq!("NewVenue", sorted_results.first().unwrap().data_source);
+  assert_eq!("OKX", sorted_results.last().unwrap().data_source);
+  // The spot pair, not the perpetual swap
+  assert_eq!("ETH-USDC", sorted_results.last().unwrap().instrument);
+}
+
+#[tokio::test]
+async fn test_registry_fetch_usd_rates() {
+  let mid_price_result = |data_source: &str, quote_ccy: &str, bid, ask| PriceResult {
+    data_source: data_source.to_string(), quote_ccy: quote_ccy.to_string(), price: ask,
+    best_bid: Some(OrderBookLevel { price: bid, size: dec!(1000) }),
+    best_ask: Some(OrderBookLevel { price: ask, size: dec!(1000) }),
+    ..Default::default()
+  };
+  let mut registry = ExchangeRegistry::default();
+  for (name, bid, ask) in [("HashKey", dec!(0.9998), dec!(1.0002)), ("Kraken", dec!(1.0001), dec!(1.0003)), ("OKX", dec!(0), dec!(0))] {
+    let mut mock = MockExchange::new();
+    mock.expect_data_source().return_const(name.to_string());
+    mock.expect_fetch_instruments().times(1).returning(move |_| Ok(match name {
+      "OKX" => vec!(InstrumentInfo::new("USDC-USDT", "USDC", "USDT", MarketType::Spot)),
+      _ => vec!(InstrumentInfo::new("USDTUSD", "USDT", "USD", MarketType::Spot), InstrumentInfo::new("USDCUSD", "USDC", "USD", MarketType::Spot))
+    }));
+    mock.expect_fetch_price().returning(move |_, symbol, _| match symbol.symbol.as_str() {
+      "USDTUSD" => Ok(mid_price_result(name, "USD", bid, ask)),
+      "USDC-USDT" => Ok(mid_price_result(name, &symbol.base_ccy, dec!(0.9990), dec!(0.9992))),
+      _ => Err(HttpError::ResponseError)
+    });
+    registry.register(Exchanges { name: name.to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }, Box::new(mock));
+  }
+  registry.load_catalogs().await;
+
+  let usd_rates = registry.fetch_usd_rates(&["USDT".to_string(), "USDC".to_string(), "USD".to_string()]).await;
+  // Median of 1.0000 and 1.0002, OKX doesn't list USDT/USD
+  assert_eq!(Some(&dec!(1.0001)), usd_rates.get("USDT"));
+  // Kraken and HashKey list USDC/USD but have no price, and the USDC/USDT price of OKX is not a USD rate
+  assert_eq!(None, usd_rates.get("USDC"));
+  assert_eq!(1, usd_rates.len());
+}
+
+#[tokio::test]
+async fn test_registry_fetch_fundings() {
+  let instrument = FundingInstrument {
+    base_ccy: "USDT".to_string(),
+    target_ccy: "BTC".to_string(),
+    alert_divergence_percent: None,
+    symbol_overrides: HashMap::new()
+  };
+
+  let mut mock_okx = MockExchange::new();
+  mock_okx.expect_data_source().return_const("OKX".to_string());
+  mock_okx.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(
+    InstrumentInfo::new("BTC-USDT", "BTC", "USDT", MarketType::Spot),
+    InstrumentInfo::new("BTC-USDT-SWAP", "BTC", "USDT", MarketType::Perp)
+  )));
+  mock_okx.expect_fetch_funding().times(1).returning(|symbol, exchange_config| Ok(
+    FundingInfo { data_source: exchange_config.name, instrument: symbol.symbol, funding_rate: dec!(0.0001), ..Default::default() }
+  ));
+
+  // Spot only
+  let mut mock_spot_venue = MockExchange::new();
+  mock_spot_venue.expect_data_source().return_const("SpotVenue".to_string());
+  mock_spot_venue.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(InstrumentInfo::new("BTCUSDT", "BTC", "USDT", MarketType::Spot))));
+  mock_spot_venue.expect_fetch_funding().never();
+
+  let mut registry = ExchangeRegistry::default();
+  registry.register(Exchanges { name: "OKX".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }, Box::new(mock_okx));
+  registry.register(Exchanges { name: "SpotVenue".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }, Box::new(mock_spot_venue));
+  registry.load_catalogs().await;
+
+  let fundings = registry.fetch_fundings(&instrument).await;
+  assert_eq!("BTC-USDT-SWAP", fundings.first().unwrap().as_ref().unwrap().instrument);
+  assert!(matches!(fundings.last().unwrap(), Err(HttpError::UnlistedInstrument(_))));
+}
+
+#[tokio::test]
+async fn test_registry_refresh_catalogs() {
+  let mut mock = MockExchange::new();
+  mock.expect_data_source().return_const("OKX".to_string());
+  mock.expect_fetch_instruments().times(1).returning(|_| Err(HttpError::ResponseError));
+  mock.expect_fetch_instruments().times(1).returning(|_| Ok(vec!(InstrumentInfo::new("ETH-USDT", "ETH", "USDT", MarketType::Spot))));
+
+  let mut registry = ExchangeRegistry::default();
+  registry.register(Exchanges { name: "OKX".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }, Box::new(mock));
+  registry.load_catalogs().await;
+  let okx = registry.exchanges.first().unwrap();
+  assert!(matches!(okx.resolve("ETH", "USDT", MarketType::Spot, None), Err(HttpError::NoInstrumentCatalog)));
+
+  // The failed list is downloaded again, a loaded one is kept until it's stale
+  registry.refresh_catalogs().await;
+  registry.refresh_catalogs().await;
+  assert_eq!("ETH-USDT", okx.resolve("ETH", "USDT", MarketType::Spot, None).unwrap().symbol);
+}
+
+#[tokio::test]
+async fn test_registry_fetch_balances() {
+  let mut mock_okx = MockExchange::new();
+  mock_okx.expect_data_source().return_const("OKX".to_string());
+  mock_okx.expect_fetch_balances().times(1).returning(|_| Ok(vec!(AssetBalance::from_raw("OKX", "USDT", "100", "0").unwrap())));
+
+  // Not monitored, so its api keys are never needed
+  let mut mock_mexc = MockExchange::new();
+  mock_mexc.expect_data_source().return_const("MEXC".to_string());
+  mock_mexc.expect_fetch_balances().never();
+
+  let mut registry = ExchangeRegistry::default();
+  registry.register(Exchanges { name: "OKX".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }, Box::new(mock_okx));
+  registry.register(Exchanges { name: "MEXC".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }, Box::new(mock_mexc));
+
+  let all_balances = registry.fetch_balances(&["okx".to_string()]).await;
+  assert_eq!(1, all_balances.len());
+  let (registered, balances) = all_balances.first().unwrap();
+  assert_eq!("OKX", registered.config.name);
+  assert_eq!(dec!(100), balances.as_ref().unwrap().first().unwrap().free);
+}
+
+#[test]
+fn test_registry_skips_unknown_exchange() {
+  let registry = ExchangeRegistry::from_config(vec!(
+    Exchanges { name: "Unknown".to_string(), trading_fee_rate: dec!(0.0), url: "https://some-url.org".to_string(), derivatives_url: None }
+  ));
+
+  assert!(registry.is_empty());
+}
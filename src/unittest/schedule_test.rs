@@ -0,0 +1,42 @@
+#[cfg(test)]
+use chrono::{TimeZone, Utc};
+#[cfg(test)]
+use crate::utils::config_struct::{Config, MonitorSchedule};
+#[cfg(test)]
+use config_file::FromConfigFile;
+
+
+#[test]
+fn test_interval_schedule() {
+  let schedule = MonitorSchedule { interval_secs: Some(60), cron: None, jitter_secs: 0 };
+  let now = Utc.with_ymd_and_hms(2024, 2, 1, 10, 0, 30).unwrap();
+
+  assert_eq!(Some(now), schedule.first_run(now));
+  assert_eq!(Some(Utc.with_ymd_and_hms(2024, 2, 1, 10, 1, 30).unwrap()), schedule.next_run(now));
+
+  // The run took 150 seconds, so the ticks at 10:01:30 and 10:02:30 are skipped
+  let finished = Utc.with_ymd_and_hms(2024, 2, 1, 10, 3, 0).unwrap();
+  assert_eq!(
+    Some((Utc.with_ymd_and_hms(2024, 2, 1, 10, 3, 30).unwrap(), 2)),
+    schedule.next_run_after(now, finished)
+  );
+}
+
+#[test]
+fn test_cron_schedule() {
+  let schedule = MonitorSchedule { interval_secs: None, cron: Some("0 */5 * * * *".to_string()), jitter_secs: 5 };
+  let now = Utc.with_ymd_and_hms(2024, 2, 1, 10, 2, 0).unwrap();
+
+  assert_eq!(Some(Utc.with_ymd_and_hms(2024, 2, 1, 10, 5, 0).unwrap()), schedule.first_run(now));
+
+  let invalid_schedule = MonitorSchedule { interval_secs: None, cron: Some("every minute".to_string()), jitter_secs: 0 };
+  assert_eq!(None, invalid_schedule.first_run(now));
+}
+
+#[test]
+fn test_config_file_schedules() {
+  let config = Config::from_config_file("config.toml").unwrap();
+
+  assert_eq!(Some(300), config.schedules.get("addresses_balances").unwrap().interval_secs);
+  assert!(!config.monitor_addresses.is_empty());
+}
@@ -0,0 +1,30 @@
+#[cfg(test)]
+use crate::exchanges::{
+  binance::connector::BinanceConnector,
+  bybit::connector::BybitConnector
+};
+
+
+#[test]
+fn test_binance_sign_query() {
+  // Example from https://binance-docs.github.io/apidocs/spot/en/#signed-endpoint-examples-for-post-api-v3-order-hmac-keys
+  let binance = BinanceConnector::new(
+    "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A".to_string(),
+    "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j".to_string()
+  );
+  let total_parameters = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+
+  let signature = binance.sign_query(total_parameters.to_string()).unwrap();
+
+  assert_eq!("c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71", signature);
+}
+
+#[test]
+fn test_bybit_sign() {
+  let bybit = BybitConnector::new("bybit_key".to_string(), "bybit_secret".to_string());
+
+  let signature = bybit.sign("accountType=UNIFIED", "1658384314791".to_string()).unwrap();
+
+  assert_eq!("1658384314791", signature.timestamp);
+  assert_eq!("46f7fbdc7a99d864c2c5c26f0c0ed19e191d1375c9fae4a3fff8996c594cc779", signature.signature);
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+use crate::unittest::http_stub::start_http_stub;
+#[cfg(test)]
+use crate::blockchain::solana::client::SolanaClient;
+#[cfg(test)]
+use crate::blockchain::solana::dto::{to_ui_amount, SOL_DECIMALS};
+
+
+/// A local stand-in of the Solana JSON-RPC, it answers by the method in the request
+#[cfg(test)]
+async fn start_rpc_stub() -> String {
+  start_http_stub(|request| {
+    if request.contains("getBalance") {
+      r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":1},"value":2500000000}}"#.to_string()
+    } else if request.contains("unknown_mint") {
+      r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"Invalid param: could not find mint"}}"#.to_string()
+    } else {
+      let token_account = |pubkey: &str, amount: &str| format!(
+        r#"{{"pubkey":"{pubkey}","account":{{"data":{{"parsed":{{"info":{{"mint":"usdc_mint","tokenAmount":{{"amount":"{amount}","decimals":6}}}}}}}}}}}}"#
+      );
+      format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"context":{{"slot":1}},"value":[{},{}]}}}}"#,
+        token_account("account_1", "1500000"), token_account("account_2", "250000")
+      )
+    }
+  }).await
+}
+
+#[tokio::test]
+async fn test_fetch_sol_balance() {
+  let client = SolanaClient::new(start_rpc_stub().await);
+
+  let lamports = client.fetch_balance("owner").await.unwrap();
+  assert_eq!(2_500_000_000, lamports);
+  assert_eq!(2.5, to_ui_amount(lamports, SOL_DECIMALS));
+}
+
+#[tokio::test]
+async fn test_fetch_token_balance() {
+  let client = SolanaClient::new(start_rpc_stub().await);
+
+  // Summed over every token account of the owner
+  let (amount, decimals) = client.fetch_token_balance("owner", "usdc_mint").await.unwrap();
+  assert_eq!(1_750_000, amount);
+  assert_eq!(Some(6), decimals);
+  assert_eq!(1.75, to_ui_amount(amount, 6));
+
+  assert!(client.fetch_token_balance("owner", "unknown_mint").await.is_err());
+}
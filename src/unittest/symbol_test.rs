@@ -0,0 +1,92 @@
+#[cfg(test)]
+use std::collections::HashMap;
+#[cfg(test)]
+use rust_decimal_macros::dec;
+#[cfg(test)]
+use crate::exchanges::symbol::{InstrumentCatalog, InstrumentInfo, MarketType};
+#[cfg(test)]
+use crate::utils::{config_struct::Instruments, error::HttpError};
+
+
+#[test]
+fn test_resolve_instrument() {
+  let catalog = InstrumentCatalog::new(vec!(
+    InstrumentInfo::new("ETH-USDT", "ETH", "USDT", MarketType::Spot),
+    InstrumentInfo { contract_value: Some(dec!(0.1)), ..InstrumentInfo::new("ETH-USDT-SWAP", "eth", "usdt", MarketType::Perp) },
+    InstrumentInfo::new("BTCUSD", "BTC", "USD", MarketType::Spot)
+  ));
+  let inst = Instruments {
+    base_ccy: "usdt".to_string(),
+    target_ccy: "eth".to_string(),
+    withdrawal_chain: "Ethereum".to_string(),
+    market_type: MarketType::Spot,
+    market_type_overrides: HashMap::new(),
+    notional_size: dec!(1000),
+    alert_spread_percent: None,
+    display_precision: None,
+    symbol_overrides: HashMap::new()
+  };
+
+  assert_eq!("ETH-USDT", catalog.resolve(&inst.target_ccy, &inst.base_ccy, MarketType::Spot, None).unwrap().symbol);
+  let perp = catalog.resolve(&inst.target_ccy, &inst.base_ccy, MarketType::Perp, None).unwrap();
+  assert_eq!("ETH-USDT-SWAP", perp.symbol);
+  assert_eq!(Some(dec!(0.1)), perp.contract_value);
+
+  // Without the USDC pair, the USD pair stands in and keeps its actual quote
+  let inst = Instruments { base_ccy: "USDC".to_string(), target_ccy: "BTC".to_string(), ..inst };
+  let fallback = catalog.resolve(&inst.target_ccy, &inst.base_ccy, MarketType::Spot, None).unwrap();
+  assert_eq!("BTCUSD", fallback.symbol);
+  assert_eq!("USD", fallback.base_ccy);
+  // Only USD-like quotes are equivalent
+  match catalog.resolve(&inst.target_ccy, "EUR", MarketType::Spot, None) {
+    Err(HttpError::UnlistedInstrument(pair)) => assert_eq!("BTC/EUR spot", pair),
+    other => panic!("Unexpected result: {:?}", other)
+  }
+
+  // An override in config.toml is used with the quote it's listed in
+  let inst = Instruments { symbol_overrides: HashMap::from([("hashkey".to_string(), "ETH-USDT".to_string())]), ..inst };
+  let symbol_override = inst.symbol_override("HashKey");
+  assert_eq!(Some(&"ETH-USDT".to_string()), symbol_override);
+  let overridden = catalog.resolve(&inst.target_ccy, &inst.base_ccy, MarketType::Spot, symbol_override).unwrap();
+  assert_eq!("ETH-USDT", overridden.symbol);
+  assert_eq!("USDT", overridden.base_ccy);
+  // An unlisted override has no known quote, so it's not guessed
+  match catalog.resolve(&inst.target_ccy, &inst.base_ccy, MarketType::Spot, Some(&"BTCUSDC".to_string())) {
+    Err(HttpError::UnlistedInstrument(symbol)) => assert_eq!("BTCUSDC", symbol),
+    other => panic!("Unexpected result: {:?}", other)
+  }
+  assert_eq!(None, inst.symbol_override("OKX"));
+}
+
+#[test]
+fn test_resolve_market_type() {
+  let futures = |symbol: &str, expiry: i64| InstrumentInfo {
+    expiry: Some(expiry), ..InstrumentInfo::new(symbol, "BTC", "USDT", MarketType::Futures)
+  };
+  let now = chrono::Utc::now().timestamp_millis();
+  let catalog = InstrumentCatalog::new(vec!(
+    InstrumentInfo::new("BTCUSDT", "BTC", "USDT", MarketType::Spot),
+    futures("BTCUSDT_NEXT_QUARTER", now + 180 * 86_400_000),
+    futures("BTCUSDT_CURRENT_QUARTER", now + 90 * 86_400_000),
+    // Delivered since the catalog was loaded
+    futures("BTCUSDT_DELIVERED", now - 86_400_000)
+  ));
+  let inst = Instruments {
+    base_ccy: "USDT".to_string(),
+    target_ccy: "BTC".to_string(),
+    withdrawal_chain: "Bitcoin".to_string(),
+    market_type: MarketType::Futures,
+    market_type_overrides: HashMap::from([("binance".to_string(), MarketType::Spot)]),
+    notional_size: dec!(1000),
+    alert_spread_percent: None,
+    display_precision: None,
+    symbol_overrides: HashMap::new()
+  };
+
+  assert_eq!(MarketType::Spot, inst.market_type("Binance"));
+  assert_eq!(MarketType::Futures, inst.market_type("OKX"));
+  // The nearest expiry that isn't delivered yet
+  assert_eq!("BTCUSDT_CURRENT_QUARTER", catalog.resolve(&inst.target_ccy, &inst.base_ccy, inst.market_type("OKX"), None).unwrap().symbol);
+  assert_eq!("BTCUSDT", catalog.resolve(&inst.target_ccy, &inst.base_ccy, inst.market_type("Binance"), None).unwrap().symbol);
+  assert!(catalog.resolve(&inst.target_ccy, &inst.base_ccy, MarketType::Perp, None).is_err());
+}
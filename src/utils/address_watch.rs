@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::config_struct::AddressWatchConfig;
+use crate::utils::json_store::JsonStore;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct AddressWatchRecord {
+    /// The last block checked for outgoing transactions
+    pub last_block: u64,
+    /// Nonce of the address at `last_block`
+    pub nonce: u64,
+    /// Nonce and unix timestamp in seconds when a pending transaction was first seen
+    pub pending_since: Option<(u64, i64)>
+}
+
+impl AddressWatchRecord {
+    /// Returns for how many seconds the transaction with `latest_nonce` has been pending, `None` if nothing is pending
+    pub fn update_pending(&mut self, latest_nonce: u64, pending_nonce: u64, now: i64) -> Option<i64> {
+        if pending_nonce <= latest_nonce {
+            self.pending_since = None;
+            return None
+        }
+
+        match self.pending_since {
+            Some((nonce, since)) if nonce == latest_nonce => Some(now - since),
+            _ => {
+                self.pending_since = Some((latest_nonce, now));
+                Some(0)
+            }
+        }
+    }
+}
+
+/// Remembers the last seen block and nonce of the watched addresses between runs, written by `flush` after each run
+pub struct AddressWatchStore {
+    records: JsonStore<AddressWatchRecord>
+}
+
+impl AddressWatchStore {
+    pub fn load(config: AddressWatchConfig) -> Self {
+        Self { records: JsonStore::load("Watch", config.path) }
+    }
+
+    pub fn get(&self, chain_id: u32, address: &str) -> Option<AddressWatchRecord> {
+        self.records.get(&watch_key(chain_id, address))
+    }
+
+    pub fn set(&self, chain_id: u32, address: &str, record: AddressWatchRecord) {
+        self.records.update(|records| {
+            let changed = records.get(&watch_key(chain_id, address)) != Some(&record);
+            records.insert(watch_key(chain_id, address), record);
+            ((), changed)
+        })
+    }
+
+    pub fn flush(&self) {
+        self.records.flush()
+    }
+}
+
+fn watch_key(chain_id: u32, address: &str) -> String {
+    format!("{chain_id}:{}", address.to_lowercase())
+}
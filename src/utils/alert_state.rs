@@ -0,0 +1,67 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::config_struct::AlertStateConfig;
+use crate::utils::json_store::JsonStore;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct AlertRecord {
+    /// Unix timestamp in seconds of the last alert sent
+    pub last_sent_at: i64,
+    /// Whether the alert condition is still ongoing
+    pub active: bool
+}
+
+/// Remembers the alerts sent so a condition that lasts across many runs is only alerted once per cooldown.
+/// Every change is written right away, so a crash never sends the same alert twice.
+pub struct AlertStore {
+    pub cooldown_secs: i64,
+    records: JsonStore<AlertRecord>
+}
+
+impl AlertStore {
+    pub fn load(config: AlertStateConfig) -> Self {
+        Self { cooldown_secs: config.cooldown_secs as i64, records: JsonStore::load("Alert", config.path) }
+    }
+
+    /// Returns true if the alert should be sent now, and records it as sent
+    pub fn should_alert(&self, monitor: &str, subject: &str) -> bool {
+        self.should_alert_at(monitor, subject, Utc::now().timestamp())
+    }
+
+    pub fn should_alert_at(&self, monitor: &str, subject: &str, now: i64) -> bool {
+        let should_alert = self.records.update(|records| {
+            let record = records.entry(alert_key(monitor, subject)).or_default();
+            let should_alert = !record.active || now - record.last_sent_at >= self.cooldown_secs;
+            let changed = should_alert || !record.active;
+            record.active = true;
+            if should_alert {
+                record.last_sent_at = now;
+            }
+            (should_alert, changed)
+        });
+        self.records.flush();
+
+        if !should_alert {
+            println!("[Alert] {monitor} {subject} was alerted within {} seconds, skipped", self.cooldown_secs);
+        }
+        should_alert
+    }
+
+    /// Mark the alert condition as over, returns true if it was active so a recovered message can be sent
+    pub fn resolve(&self, monitor: &str, subject: &str) -> bool {
+        let resolved = self.records.update(|records| match records.get_mut(&alert_key(monitor, subject)) {
+            Some(record) if record.active => {
+                record.active = false;
+                (true, true)
+            }
+            _ => (false, false)
+        });
+        self.records.flush();
+        resolved
+    }
+}
+
+fn alert_key(monitor: &str, subject: &str) -> String {
+    format!("{monitor}:{subject}")
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::config_struct::BalanceHistoryConfig;
+use crate::utils::json_store::JsonStore;
+
+/// The burn rate is estimated from the samples of the last 24 hours
+pub const BURN_RATE_LOOKBACK_SECS: i64 = 24 * 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct BalanceSample {
+    /// Unix timestamp in seconds
+    pub timestamp: i64,
+    pub balance: f64
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceDrop {
+    /// The balance compared with, either the previous run or the highest one in the window
+    pub reference: BalanceSample,
+    pub amount: f64,
+    pub percent: f64
+}
+
+/// Balance samples of every monitored address between runs, written by `flush` after each run
+pub struct BalanceHistoryStore {
+    pub retention_secs: i64,
+    records: JsonStore<Vec<BalanceSample>>
+}
+
+impl BalanceHistoryStore {
+    pub fn load(config: BalanceHistoryConfig) -> Self {
+        Self { retention_secs: config.retention_secs as i64, records: JsonStore::load("History", config.path) }
+    }
+
+    /// Samples from the oldest to the newest
+    pub fn history(&self, subject: &str) -> Vec<BalanceSample> {
+        self.records.get(subject).unwrap_or_default()
+    }
+
+    /// Append a sample and drop the ones older than the retention
+    pub fn record(&self, subject: &str, sample: BalanceSample) {
+        self.records.update(|records| {
+            let samples = records.entry(subject.to_string()).or_default();
+            samples.push(sample);
+            samples.retain(|item| sample.timestamp - item.timestamp <= self.retention_secs);
+            ((), true)
+        })
+    }
+
+    pub fn flush(&self) {
+        self.records.flush()
+    }
+}
+
+/// Compare with the previous sample, or with the highest balance within `window_secs` if it's set.
+/// Without any sample within the window there is nothing to compare with, so no drop is reported.
+pub fn find_balance_drop(history: &[BalanceSample], current: BalanceSample, window_secs: Option<u64>) -> Option<BalanceDrop> {
+    let reference = match window_secs {
+        Some(window_secs) => history.iter()
+            .filter(|sample| current.timestamp - sample.timestamp <= window_secs as i64)
+            .copied()
+            .reduce(|highest, sample| if sample.balance > highest.balance { sample } else { highest })?,
+        None => *history.last()?
+    };
+
+    let amount = reference.balance - current.balance;
+    if amount <= 0.0 {
+        return None
+    }
+    let percent = if reference.balance > 0.0 { amount / reference.balance * 100.0 } else { 0.0 };
+    Some(BalanceDrop { reference, amount, percent })
+}
+
+/// Spent per hour within the lookback, top-ups are not counted as negative spending
+pub fn burn_rate_per_hour(history: &[BalanceSample], current: BalanceSample) -> Option<f64> {
+    let samples: Vec<BalanceSample> = history.iter()
+        .filter(|sample| current.timestamp - sample.timestamp <= BURN_RATE_LOOKBACK_SECS)
+        .copied()
+        .chain([current])
+        .collect();
+
+    let elapsed_secs = current.timestamp - samples.first()?.timestamp;
+    if elapsed_secs <= 0 {
+        return None
+    }
+    let spent: f64 = samples.windows(2)
+        .map(|pair| (pair[0].balance - pair[1].balance).max(0.0))
+        .sum();
+    if spent <= 0.0 {
+        return None
+    }
+    Some(spent / elapsed_secs as f64 * 3600.0)
+}
+
+/// Hours until the balance runs out at the burn rate
+pub fn hours_left(balance: f64, burn_rate_per_hour: f64) -> f64 {
+    (balance / burn_rate_per_hour).max(0.0)
+}
@@ -1,22 +1,312 @@
+use std::collections::HashMap;
+use rust_decimal::Decimal;
 use serde::Deserialize;
 
+use crate::exchanges::symbol::MarketType;
+
 #[derive(Deserialize, Clone)]
 pub struct Config {
+    #[serde(default = "default_notifiers")]
+    pub notifiers: Vec<NotifierChannel>,
+    /// Schedules of `--daemon` mode keyed by monitor name
+    #[serde(default)]
+    pub schedules: HashMap<String, MonitorSchedule>,
+    #[serde(default)]
+    pub alert_state: AlertStateConfig,
+    #[serde(default)]
+    pub address_watch: AddressWatchConfig,
+    #[serde(default)]
+    pub balance_history: BalanceHistoryConfig,
+    #[serde(default)]
+    pub bitcoin: BitcoinConfig,
+    #[serde(default)]
+    pub solana: SolanaConfig,
+    /// RPC settings by chain id, the chains without settings use `NODE_URL_{chain_id}` env vars
+    #[serde(default)]
+    pub node_providers: HashMap<String, NodeProviderConfig>,
+    /// Override or add chains of the built-in chain registry by chain id
+    #[serde(default)]
+    pub chains: HashMap<String, ChainConfig>,
     pub monitor_addresses: Vec<MonitorAddress>,
-    pub exchange_difference: ExchangeDifference
+    #[serde(default)]
+    pub monitor_gas_prices: Vec<MonitorGasPrice>,
+    /// Account balances on the exchanges of `exchange_difference`
+    #[serde(default)]
+    pub monitor_exchange_balances: Vec<MonitorExchangeBalance>,
+    pub exchange_difference: ExchangeDifference,
+    /// Perpetuals compared on the exchanges of `exchange_difference`
+    #[serde(default)]
+    pub funding_rates: FundingRates
+}
+
+/// One channel to send alerts to, secrets are read from the environment variables named here
+#[derive(Deserialize, Clone, Debug)]
+pub struct NotifierChannel {
+    /// telegram, discord, slack or webhook
+    pub channel: String,
+    /// Telegram only, default TELEGRAM_CHAT_ID
+    pub chat_id_env: Option<String>,
+    /// Telegram only, default TELEGRAM_TOKEN
+    pub token_env: Option<String>,
+    /// Discord, Slack and webhook only
+    pub webhook_url_env: Option<String>
+}
+
+/// Either `interval_secs` or `cron` (with seconds, e.g. "0 */5 * * * *") should be set
+#[derive(Deserialize, Clone, Debug)]
+pub struct MonitorSchedule {
+    pub interval_secs: Option<u64>,
+    pub cron: Option<String>,
+    /// Random delay up to this many seconds added before each run
+    #[serde(default)]
+    pub jitter_secs: u64
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct ChainConfig {
+    pub name: Option<String>,
+    pub native_symbol: Option<String>,
+    pub decimals: Option<u8>,
+    pub block_time_secs: Option<f64>,
+    /// Explorer url with an `{address}` placeholder
+    pub address_url_template: Option<String>,
+    /// Explorer url with a `{tx_hash}` placeholder
+    pub tx_url_template: Option<String>
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct NodeProviderConfig {
+    /// Env vars of the RPC urls in failover order, default to `NODE_URL_{chain_id}`, `NODE_URL_{chain_id}_1`...
+    #[serde(default)]
+    pub urls_env: Vec<String>,
+    #[serde(default = "default_node_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub quorum: bool,
+    /// The node is stale if its latest block is older, default to 20 blocks of the chain
+    pub max_block_age_secs: Option<u64>,
+    /// Alert when the latest block takes longer to fetch
+    pub max_latency_ms: Option<u64>
+}
+
+impl Default for NodeProviderConfig {
+    fn default() -> Self {
+        Self {
+            urls_env: vec!(),
+            timeout_secs: default_node_timeout_secs(),
+            quorum: false,
+            max_block_age_secs: None,
+            max_latency_ms: None
+        }
+    }
+}
+
+fn default_node_timeout_secs() -> u64 {
+    10
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AlertStateConfig {
+    /// JSON file to remember the alerts sent
+    #[serde(default = "default_alert_state_path")]
+    pub path: String,
+    /// The same alert is sent again only after this many seconds
+    #[serde(default = "default_alert_cooldown_secs")]
+    pub cooldown_secs: u64
+}
+
+impl Default for AlertStateConfig {
+    fn default() -> Self {
+        Self { path: default_alert_state_path(), cooldown_secs: default_alert_cooldown_secs() }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BitcoinConfig {
+    /// Base url of an Esplora compatible api
+    #[serde(default = "default_esplora_url")]
+    pub esplora_url: String,
+    /// Compare `alert_threshold` with the confirmed plus unconfirmed balance
+    #[serde(default)]
+    pub include_unconfirmed: bool
+}
+
+impl Default for BitcoinConfig {
+    fn default() -> Self {
+        Self { esplora_url: default_esplora_url(), include_unconfirmed: false }
+    }
+}
+
+fn default_esplora_url() -> String {
+    "https://blockstream.info/api".to_string()
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct SolanaConfig {
+    /// Env var of the JSON-RPC url
+    #[serde(default = "default_solana_rpc_url_env")]
+    pub rpc_url_env: String
+}
+
+impl Default for SolanaConfig {
+    fn default() -> Self {
+        Self { rpc_url_env: default_solana_rpc_url_env() }
+    }
+}
+
+fn default_solana_rpc_url_env() -> String {
+    "SOLANA_RPC_URL".to_string()
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BalanceHistoryConfig {
+    /// JSON file to remember the balances of the monitored addresses
+    #[serde(default = "default_balance_history_path")]
+    pub path: String,
+    /// Samples older than this are dropped
+    #[serde(default = "default_balance_history_retention_secs")]
+    pub retention_secs: u64
+}
+
+impl Default for BalanceHistoryConfig {
+    fn default() -> Self {
+        Self { path: default_balance_history_path(), retention_secs: default_balance_history_retention_secs() }
+    }
+}
+
+fn default_balance_history_path() -> String {
+    "balance_history.json".to_string()
+}
+
+fn default_balance_history_retention_secs() -> u64 {
+    7 * 24 * 3600
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AddressWatchConfig {
+    /// JSON file to remember the last seen block and nonce of the watched addresses
+    #[serde(default = "default_address_watch_path")]
+    pub path: String
+}
+
+impl Default for AddressWatchConfig {
+    fn default() -> Self {
+        Self { path: default_address_watch_path() }
+    }
+}
+
+fn default_address_watch_path() -> String {
+    "address_watch.json".to_string()
+}
+
+fn default_alert_state_path() -> String {
+    "alert_state.json".to_string()
+}
+
+fn default_alert_cooldown_secs() -> u64 {
+    3600
+}
+
+fn default_notifiers() -> Vec<NotifierChannel> {
+    vec!(NotifierChannel { channel: "telegram".to_string(), chat_id_env: None, token_env: None, webhook_url_env: None })
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MonitorGasPrice {
+    pub chain_id: u32,
+    /// Alert when base fee plus priority fee is lower, a good time to top up
+    pub below_gwei: Option<f64>,
+    /// Alert when base fee plus priority fee is higher
+    pub above_gwei: Option<f64>
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MonitorExchangeBalance {
+    /// Name of one of `exchange_difference.exchanges`
+    pub exchange: String,
+    pub asset: String,
+    pub alert_threshold: Decimal,
+    /// Compare free plus locked amounts with the threshold, only the free amount by default
+    #[serde(default)]
+    pub include_locked: bool
+}
+
+impl MonitorExchangeBalance {
+    /// Identify the exchange balance in the alert state
+    pub fn alert_subject(&self) -> String {
+        format!("{}:{}", self.exchange.to_ascii_lowercase(), self.asset.to_ascii_uppercase())
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainType {
+    #[default]
+    Evm,
+    Bitcoin,
+    Solana
 }
 
 #[derive(Deserialize, Clone)]
 pub struct MonitorAddress {
     pub name: String,
+    /// An xpub, ypub or zpub is also accepted for bitcoin
     pub address: String,
+    #[serde(default)]
+    pub chain_type: ChainType,
     pub alert_threshold: f64,
-    pub symbol: String,
-    pub chain_id: u32
+    /// Default to the native symbol of the chain, or the ERC-20 symbol of `token_contract`
+    pub symbol: Option<String>,
+    /// Only used by EVM chains
+    #[serde(default)]
+    pub chain_id: u32,
+    /// ERC-20 contract or SPL token mint to monitor, the native coin is monitored if it's not set
+    pub token_contract: Option<String>,
+    /// Decimals of the monitored balance: of `token_contract` if it's set, otherwise of the native coin.
+    /// Token decimals are read from the chain and native decimals come from `[chains]` if it's not set
+    pub decimals: Option<u8>,
+    /// Alert on every outgoing transaction of the address
+    #[serde(default)]
+    pub watch_outgoing: bool,
+    /// Alert on outgoing transfers above this value in the native coin
+    pub max_transfer_value: Option<f64>,
+    /// Alert when a transaction of the address is pending for longer
+    pub max_pending_secs: Option<u64>,
+    /// Alert when the balance drops by this percent
+    pub max_drop_percent: Option<f64>,
+    /// Alert when the balance drops by this amount
+    pub max_drop_amount: Option<f64>,
+    /// Compare with the highest balance within this window instead of the previous run
+    pub drop_window_secs: Option<u64>,
+    /// Stop deriving addresses of a bitcoin xpub after these many unused ones in a row, default to 20 and at least 1
+    pub gap_limit: Option<u32>
+}
+
+impl MonitorAddress {
+    pub fn is_watched(&self) -> bool {
+        self.chain_type == ChainType::Evm
+            && (self.watch_outgoing || self.max_transfer_value.is_some() || self.max_pending_secs.is_some())
+    }
+
+    /// Identify the address in the alert state.
+    pub fn alert_subject(&self) -> String {
+        let subject = match self.chain_type {
+            ChainType::Evm => format!("{}:{}", self.chain_id, self.address),
+            ChainType::Bitcoin => format!("bitcoin:{}", self.address),
+            ChainType::Solana => format!("solana:{}", self.address)
+        };
+        // The native coin and tokens of the same address are alerted separately
+        match &self.token_contract {
+            Some(token_contract) => format!("{subject}:{token_contract}"),
+            None => subject
+        }
+    }
 }
 
 #[derive(Deserialize, Clone)]
 pub struct ExchangeDifference {
+    /// Default net spread in percent to send an alert, can be overridden by each instrument
+    pub alert_spread_percent: Option<Decimal>,
     pub exchanges: Vec<Exchanges>,
     pub instruments: Vec<Instruments>
 }
@@ -25,12 +315,92 @@ pub struct ExchangeDifference {
 pub struct Exchanges {
     pub name: String,
     pub url: String,
-    pub trading_fee_rate: f32
+    /// Base url of the perpetual and futures api if it's not `url`, e.g. Binance and MEXC
+    pub derivatives_url: Option<String>,
+    pub trading_fee_rate: Decimal
 }
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct Instruments {
     pub base_ccy: String,
     pub target_ccy: String,
-    pub withdrawal_chain: String
+    pub withdrawal_chain: String,
+    /// Product compared on every exchange, spot by default
+    #[serde(default)]
+    pub market_type: MarketType,
+    /// Market type by exchange name, to compare spot on one exchange with perpetuals on another one on purpose
+    #[serde(default)]
+    pub market_type_overrides: HashMap<String, MarketType>,
+    /// Amount in base_ccy used to calculate the effective buy and sell price from order books
+    #[serde(default = "default_notional_size")]
+    pub notional_size: Decimal,
+    /// Net spread in percent to send an alert, fallback to `ExchangeDifference.alert_spread_percent`
+    pub alert_spread_percent: Option<Decimal>,
+    /// Decimal places of the prices and spreads in the report and alerts, every significant digit if it's not set
+    pub display_precision: Option<u32>,
+    /// Native symbol by exchange name, to pick another listed pair than the one resolved from the instrument list
+    #[serde(default)]
+    pub symbol_overrides: HashMap<String, String>
+}
+
+impl Instruments {
+    /// Spot pair without alert or overrides, for prices the monitors need on their own like USD rates
+    pub fn spot(target_ccy: &str, base_ccy: &str, notional_size: Decimal) -> Self {
+        Self {
+            base_ccy: base_ccy.to_string(),
+            target_ccy: target_ccy.to_string(),
+            withdrawal_chain: String::new(),
+            market_type: MarketType::Spot,
+            market_type_overrides: HashMap::new(),
+            notional_size,
+            alert_spread_percent: None,
+            display_precision: None,
+            symbol_overrides: HashMap::new()
+        }
+    }
+
+    pub fn market_type(&self, exchange_name: &str) -> MarketType {
+        find_by_exchange(&self.market_type_overrides, exchange_name).map_or(self.market_type, |market_type| *market_type)
+    }
+
+    pub fn symbol_override(&self, exchange_name: &str) -> Option<&String> {
+        find_by_exchange(&self.symbol_overrides, exchange_name)
+    }
+}
+
+/// Exchange names are case insensitive in config.toml
+fn find_by_exchange<'a, T>(values: &'a HashMap<String, T>, exchange_name: &str) -> Option<&'a T> {
+    values.iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(exchange_name))
+        .map(|(_, value)| value)
+}
+
+fn default_notional_size() -> Decimal {
+    Decimal::ONE_THOUSAND
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct FundingRates {
+    /// Default gap in percent between the highest and the lowest annualized funding to send an alert
+    pub alert_divergence_percent: Option<Decimal>,
+    #[serde(default)]
+    pub instruments: Vec<FundingInstrument>
+}
+
+/// A linear perpetual, e.g. target_ccy BTC and base_ccy USDT
+#[derive(Deserialize, Clone, Debug)]
+pub struct FundingInstrument {
+    pub base_ccy: String,
+    pub target_ccy: String,
+    /// Fallback to `FundingRates.alert_divergence_percent`
+    pub alert_divergence_percent: Option<Decimal>,
+    /// Native symbol by exchange name, like `Instruments.symbol_overrides`
+    #[serde(default)]
+    pub symbol_overrides: HashMap<String, String>
+}
+
+impl FundingInstrument {
+    pub fn symbol_override(&self, exchange_name: &str) -> Option<&String> {
+        find_by_exchange(&self.symbol_overrides, exchange_name)
+    }
 }
@@ -26,5 +26,33 @@ pub enum HttpError {
     ResponseError,
     
     #[error("Response data doesn't match usable patten")]
-    ResponseDataError
+    ResponseDataError,
+
+    #[error("The exchange doesn't support this request")]
+    Unsupported,
+
+    #[error("The exchange doesn't list {0}")]
+    UnlistedInstrument(String),
+
+    #[error("Instrument list of the exchange is not loaded")]
+    NoInstrumentCatalog,
+
+    #[error("No rate to convert {0} to {1}")]
+    NoQuoteRate(String, String),
+
+    #[error("Environment variables {0} are not set")]
+    MissingApiKey(String)
+}
+
+/// Error type for blockchain nodes.
+#[derive(Debug, Error, Clone)]
+pub enum NodeError {
+    #[error("No node url of chain id {0} is configured")]
+    NoEndpoint(u32),
+
+    #[error("All nodes of chain id {0} failed")]
+    AllEndpointsFailed(u32),
+
+    #[error("Nodes of chain id {0} don't agree on the result at block {1}")]
+    NoQuorum(u32, u64)
 }
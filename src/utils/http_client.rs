@@ -2,6 +2,7 @@ use reqwest::{
     Client,
     header::HeaderMap
 };
+use serde_json::Value;
 use crate::utils::error::HttpError;
 
 pub struct HttpClient {
@@ -31,4 +32,16 @@ impl HttpClient {
                 HttpError::RequestError
             })
     }
+
+    pub async fn post_json(&self, url: String, body: &Value) -> Result<reqwest::Response, HttpError> {
+        self.client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|error| {
+                eprintln!("[{} error] {}", self.data_source, error);
+                HttpError::RequestError
+            })
+    }
 }
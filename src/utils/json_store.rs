@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+struct StoreState<T> {
+    records: HashMap<String, T>,
+    /// Whether the records changed since the file was written
+    dirty: bool
+}
+
+/// Records keyed by string shared by the monitors of one process, persisted as a JSON file so the state works for
+/// one-shot runs as well as `--daemon` mode. Updates stay in memory until `flush`, so a run writes the file once.
+pub struct JsonStore<T> {
+    pub path: String,
+    /// Prefix of the log lines, e.g. `Alert`
+    name: &'static str,
+    state: Mutex<StoreState<T>>
+}
+
+impl<T: Clone + Serialize + DeserializeOwned> JsonStore<T> {
+    /// A missing or unreadable file starts an empty store
+    pub fn load(name: &'static str, path: String) -> Self {
+        let records = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<HashMap<String, T>>(&content).ok())
+            .unwrap_or_default();
+
+        Self { path, name, state: Mutex::new(StoreState { records, dirty: false }) }
+    }
+
+    pub fn get(&self, key: &str) -> Option<T> {
+        self.state.lock().unwrap().records.get(key).cloned()
+    }
+
+    /// Change the records in memory, `update` returns true if it changed anything
+    pub fn update<R>(&self, update: impl FnOnce(&mut HashMap<String, T>) -> (R, bool)) -> R {
+        let mut state = self.state.lock().unwrap();
+        let (result, changed) = update(&mut state.records);
+        state.dirty |= changed;
+        result
+    }
+
+    /// Write the records to the file if they changed since the last flush
+    pub fn flush(&self) {
+        let mut state = self.state.lock().unwrap();
+        if !state.dirty {
+            return
+        }
+        let content = serde_json::to_string_pretty(&state.records).expect("Failed to serialize the records");
+        match fs::write(&self.path, content) {
+            Ok(_) => state.dirty = false,
+            Err(e) => eprintln!("[{}] Failed to save the state to {}: {e}", self.name, self.path)
+        }
+    }
+}
@@ -1,47 +1,129 @@
 use std::env;
 use serde_json::{json, Value};
 
-pub async fn send_one_message(message_text: String, chat_id: Option<String>, bot_token: Option<String>) {
-    let formatted_message = message_text
-        .replace(".", "\\.")
-        .replace("(", "\\(")
-        .replace(")", "\\)");
+use crate::utils::error::HttpError;
 
-    let chat_id_env_var = env::var(chat_id.unwrap_or("TELEGRAM_CHAT_ID".parse().unwrap())).unwrap();
-    let token = env::var(bot_token.unwrap_or("TELEGRAM_TOKEN".parse().unwrap())).unwrap();
+/// Characters reserved by Telegram MarkdownV2, reference: https://core.telegram.org/bots/api#markdownv2-style
+const RESERVED_CHARS: &str = "_*[]()~`>#+-=|{}.!\\";
+/// Formatting the alerts use on purpose: *bold*, _italic_, __underline__, ~strikethrough~, ||spoiler|| and `code`
+const MARKERS: &str = "*_~`";
 
-    let body: Value;
+/// Escape the reserved characters of a message except the formatting markers.
+/// Characters already escaped with `\` are kept as is, and the content of `code` is never escaped.
+pub fn format_markdown_v2(message: &str) -> String {
+    let mut formatted = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    let mut in_code = false;
 
-    if chat_id_env_var.contains("/") {
-        let chat_info = chat_id_env_var.split("/").collect::<Vec<&str>>();
-        let chat_id = chat_info.get(0).unwrap();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                formatted.push(c);
+                formatted.extend(chars.next());
+            }
+            '`' => {
+                in_code = !in_code;
+                formatted.push(c);
+            }
+            _ if in_code => formatted.push(c),
+            '|' if chars.peek() == Some(&'|') => {
+                chars.next();
+                formatted.push_str("||");
+            }
+            _ if MARKERS.contains(c) => formatted.push(c),
+            _ if RESERVED_CHARS.contains(c) => {
+                formatted.push('\\');
+                formatted.push(c);
+            }
+            _ => formatted.push(c)
+        }
+    }
+
+    formatted
+}
+
+/// Escape every reserved character of user supplied text like address names, so none of them is taken as a marker
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if RESERVED_CHARS.contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Convert the formatting markers (`__`, `||` or one of `*_~`) for the channels that don't use MarkdownV2,
+/// the escaped characters are unescaped and never taken as markers
+pub fn replace_markers(message: &str, replace: impl Fn(&str) -> &str) -> String {
+    let mut replaced = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek().is_some_and(|next| RESERVED_CHARS.contains(*next)) => replaced.extend(chars.next()),
+            '_' | '|' if chars.peek() == Some(&c) => {
+                chars.next();
+                replaced.push_str(replace(if c == '_' { "__" } else { "||" }));
+            }
+            '*' | '_' | '~' => replaced.push_str(replace(c.encode_utf8(&mut [0; 4]))),
+            _ => replaced.push(c)
+        }
+    }
+
+    replaced
+}
+
+pub async fn send_one_message(message_text: String, chat_id: Option<String>, bot_token: Option<String>) -> Result<(), HttpError> {
+    let formatted_message = format_markdown_v2(&message_text);
+
+    let chat_id_env = chat_id.unwrap_or("TELEGRAM_CHAT_ID".to_string());
+    let token_env = bot_token.unwrap_or("TELEGRAM_TOKEN".to_string());
+    let (chat_id_env_var, token) = match (env::var(&chat_id_env), env::var(&token_env)) {
+        (Ok(chat_id), Ok(token)) => (chat_id, token),
+        _ => return Err(HttpError::MissingApiKey(format!("{chat_id_env} and {token_env}")))
+    };
+
+    let body: Value = if chat_id_env_var.contains('/') {
+        let chat_info = chat_id_env_var.split('/').collect::<Vec<&str>>();
+        let chat_id = chat_info.first().unwrap();
         let reply_to_message_id = chat_info.get(1).unwrap();
-        body = json!({
+        json!({
             "chat_id": chat_id,
             "text": formatted_message,
             "parse_mode": "MarkdownV2",
             "reply_to_message_id": reply_to_message_id
-        });
+        })
     } else {
-        body = json!({
+        json!({
             "chat_id": chat_id_env_var,
             "text": formatted_message,
             "parse_mode": "MarkdownV2",
-        });
+        })
     };
 
     println!("[Messenger] Sending one message (formatted) >> {body}\n");
 
     let client = reqwest::Client::new();
     // https://core.telegram.org/bots/api#available-methods
-    let response = client.post(&format!("https://api.telegram.org/bot{token}/sendMessage"))
+    let response = client.post(format!("https://api.telegram.org/bot{token}/sendMessage"))
         .json(&body)
         .send()
         .await;
 
-    if response.is_ok() {
-        println!("[Messenger] Message sent successfully!")
-    } else {
-        eprintln!("[Messenger] Failed to send message. Status: {:?}", response);
+    match response {
+        Ok(response) if response.status().is_success() => {
+            println!("[Messenger] Message sent successfully!");
+            Ok(())
+        }
+        Ok(response) => {
+            eprintln!("[Messenger] Failed to send message. Status: {:?}", response);
+            Err(HttpError::ResponseError)
+        }
+        Err(e) => {
+            eprintln!("[Messenger] Failed to send message: {e}");
+            Err(HttpError::RequestError)
+        }
     }
 }
@@ -3,3 +3,7 @@ pub mod messenger;
 pub mod http_client;
 pub mod error;
 pub mod number_utils;
+pub mod json_store;
+pub mod alert_state;
+pub mod address_watch;
+pub mod balance_history;
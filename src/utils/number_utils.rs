@@ -1,9 +1,197 @@
-use crate::exchanges::dto::PriceResult;
+use std::collections::HashMap;
+use std::str::FromStr;
+use rust_decimal::Decimal;
+
+use crate::exchanges::dto::{ArbitrageOpportunity, NetworkInfo, OrderBook, OrderBookLevel, PriceResult};
+use crate::exchanges::symbol::MarketType;
 use crate::utils::error::HttpError;
 
-pub fn calculate_price_with_trading_fee(data_source: String, price: String, fee_rate: f32) -> f32 {
-    let price_number = price.parse::<f32>().expect(&*format!("[{data_source}] Failed to parse string to number"));
-    price_number * (1.0 + fee_rate / 100.0)
+const HUNDRED: Decimal = Decimal::ONE_HUNDRED;
+/// Pivot of the quote currency rates
+pub const USD: &str = "USD";
+
+/// Parse numbers returned by exchanges, including the scientific notation like `1e-8`
+pub fn parse_decimal(value: &str) -> Option<Decimal> {
+    let value = value.trim();
+    Decimal::from_str(value).ok().or_else(|| Decimal::from_scientific(value).ok())
+}
+
+/// Display with `precision` decimal places, or every significant digit if it's not set
+pub fn format_decimal(value: Decimal, precision: Option<u32>) -> String {
+    match precision {
+        Some(precision) => format!("{:.*}", precision as usize, value.round_dp(precision)),
+        None => value.normalize().to_string()
+    }
+}
+
+pub fn calculate_price_with_trading_fee(data_source: String, price: String, fee_rate: Decimal) -> Result<Decimal, HttpError> {
+    let price_number = parse_decimal(&price).ok_or_else(|| {
+        eprintln!("[{data_source}] Failed to parse price {price} to number");
+        HttpError::ResponseDataError
+    })?;
+    Ok(price_number * (Decimal::ONE + fee_rate / HUNDRED))
+}
+
+/// Volume weighted average price of filling `notional_size` (in base currency) by walking the order book levels,
+/// `None` if the order book is not deep enough
+pub fn calculate_effective_price(levels: &[OrderBookLevel], notional_size: Decimal) -> Option<Decimal> {
+    let mut remaining = notional_size;
+    let mut filled_quantity = Decimal::ZERO;
+
+    for level in levels.iter().filter(|level| level.price > Decimal::ZERO) {
+        if remaining <= Decimal::ZERO {
+            break
+        }
+        let taken_notional = remaining.min(level.price * level.size);
+        filled_quantity += taken_notional / level.price;
+        remaining -= taken_notional;
+    }
+
+    if remaining > Decimal::ZERO {
+        None
+    } else {
+        notional_size.checked_div(filled_quantity)
+    }
+}
+
+/// Effective buy (asks) and sell (bids) price of the order book
+pub fn calculate_effective_prices(data_source: String, order_book: &OrderBook, notional_size: Decimal) -> (Option<Decimal>, Option<Decimal>) {
+    let buy_price = calculate_effective_price(&order_book.asks, notional_size);
+    let sell_price = calculate_effective_price(&order_book.bids, notional_size);
+
+    if buy_price.is_none() || sell_price.is_none() {
+        eprintln!("[{data_source}] Order book is not deep enough for notional size {notional_size}");
+    }
+
+    (buy_price, sell_price)
+}
+
+/// Compare every pair of different venues, buying at the effective ask of one and selling at the effective bid of
+/// another, and return the actionable one with the largest net spread after trading and withdrawal fees.
+/// `network_infos` is keyed by data source, a transfer from or to a venue without network info is not actionable
+/// because it may be closed. Nothing is transferred when either side is a derivative, so the network infos are
+/// only used between spot markets.
+pub fn find_arbitrage_opportunity(
+    price_results: &[PriceResult], network_infos: &HashMap<String, NetworkInfo>, notional_size: Decimal
+) -> Option<ArbitrageOpportunity> {
+    let mut best_opportunity: Option<ArbitrageOpportunity> = None;
+
+    for buy_result in price_results {
+        for sell_result in price_results {
+            if buy_result.data_source == sell_result.data_source {
+                continue
+            }
+            if let (Some(buy_price), Some(sell_price)) = (buy_result.buy_price, sell_result.sell_price) {
+                let transfer = (buy_result.market_type == MarketType::Spot && sell_result.market_type == MarketType::Spot).then(
+                    || (network_infos.get(&buy_result.data_source), network_infos.get(&sell_result.data_source))
+                );
+                let opportunity = calculate_arbitrage_opportunity(
+                    buy_result, sell_result, buy_price, sell_price, transfer, notional_size
+                );
+                let is_better = best_opportunity.as_ref().is_none_or(
+                    |best| (opportunity.is_actionable(), opportunity.net_spread) > (best.is_actionable(), best.net_spread)
+                );
+                if is_better {
+                    best_opportunity = Some(opportunity);
+                }
+            }
+        }
+    }
+
+    best_opportunity
+}
+
+fn calculate_arbitrage_opportunity(
+    buy_result: &PriceResult,
+    sell_result: &PriceResult,
+    buy_price: Decimal,
+    sell_price: Decimal,
+    transfer: Option<(Option<&NetworkInfo>, Option<&NetworkInfo>)>,
+    notional_size: Decimal
+) -> ArbitrageOpportunity {
+    let gross_spread = sell_price - buy_price;
+    let buy_cost = buy_price * (Decimal::ONE + buy_result.trading_fee_rate / HUNDRED);
+    let sell_proceeds = sell_price * (Decimal::ONE - sell_result.trading_fee_rate / HUNDRED);
+
+    // The withdrawal fee is paid once in coins, spread it over the quantity bought with the notional size
+    let withdrawal_fee = transfer.and_then(|(buy_network, _)| buy_network).map(|network| network.withdraw_fee);
+    let quantity = notional_size.checked_div(buy_price).unwrap_or_default();
+    let withdrawal_cost = (withdrawal_fee.unwrap_or_default() * sell_proceeds).checked_div(quantity).unwrap_or_default();
+    let net_spread = sell_proceeds - buy_cost - withdrawal_cost;
+
+    let non_actionable_reason = match transfer {
+        Some((Some(network), _)) if !network.withdraw_enable =>
+            Some(format!("Withdrawal of {} on {} is disabled", network.chain, network.data_source)),
+        Some((_, Some(network))) if !network.deposit_enable =>
+            Some(format!("Deposit of {} on {} is disabled", network.chain, network.data_source)),
+        Some((None, _)) => Some(format!("Withdrawal network on {} is unknown", buy_result.data_source)),
+        Some((_, None)) => Some(format!("Deposit network on {} is unknown", sell_result.data_source)),
+        _ => None
+    };
+
+    ArbitrageOpportunity {
+        buy: buy_result.clone(),
+        sell: sell_result.clone(),
+        buy_price,
+        sell_price,
+        gross_spread,
+        gross_percent: gross_spread.checked_div(buy_price).unwrap_or_default() * HUNDRED,
+        withdrawal_fee,
+        net_spread,
+        net_percent: net_spread.checked_div(buy_cost).unwrap_or_default() * HUNDRED,
+        non_actionable_reason
+    }
+}
+
+/// Middle value of the rates quoted by different venues, so one off-market venue can't skew it
+pub fn median(mut values: Vec<Decimal>) -> Option<Decimal> {
+    values.sort();
+    let middle = values.len() / 2;
+    match values.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((values[middle - 1] + values[middle]) / Decimal::TWO),
+        _ => Some(values[middle])
+    }
+}
+
+/// Convert the prices quoted in other currencies to `quote_ccy` through their USD rates, e.g. a USD pair to USDC.
+/// `usd_rates` is keyed by currency, USD itself is always 1. Results without a rate become `HttpError::NoQuoteRate`.
+pub fn normalize_quotes(
+    all_results: Vec<Result<PriceResult, HttpError>>, quote_ccy: &str, usd_rates: &HashMap<String, Decimal>
+) -> Vec<Result<PriceResult, HttpError>> {
+    let quote_ccy = quote_ccy.to_ascii_uppercase();
+    let usd_rate = |ccy: &str| if ccy == USD { Some(Decimal::ONE) } else { usd_rates.get(ccy).copied() };
+
+    all_results.into_iter().map(|result| {
+        let price_result = result?;
+        let raw_quote_ccy = price_result.quote_ccy.to_ascii_uppercase();
+        if raw_quote_ccy == quote_ccy {
+            return Ok(price_result)
+        }
+        let rate = usd_rate(&raw_quote_ccy)
+            .zip(usd_rate(&quote_ccy))
+            .and_then(|(raw_usd_rate, usd_rate)| raw_usd_rate.checked_div(usd_rate))
+            .ok_or(HttpError::NoQuoteRate(raw_quote_ccy, quote_ccy.clone()))?;
+        Ok(price_result.normalize(&quote_ccy, rate))
+    }).collect()
+}
+
+/// Quote currencies of the results that need a USD rate to be converted to `quote_ccy`, including `quote_ccy` itself
+pub fn quote_ccys_to_normalize(all_results: &[Result<PriceResult, HttpError>], quote_ccy: &str) -> Vec<String> {
+    let quote_ccy = quote_ccy.to_ascii_uppercase();
+    let mut currencies: Vec<String> = all_results.iter()
+        .flat_map(|result| result.as_ref().ok())
+        .map(|price_result| price_result.quote_ccy.to_ascii_uppercase())
+        .filter(|ccy| *ccy != quote_ccy)
+        .collect();
+    if currencies.is_empty() {
+        return currencies
+    }
+    currencies.push(quote_ccy);
+    currencies.sort();
+    currencies.dedup();
+    currencies.retain(|ccy| ccy != USD);
+    currencies
 }
 
 /// Sort price result by price in descending order
@@ -13,6 +201,6 @@ pub fn sort_price_result(all_results: Vec<Result<PriceResult, HttpError>>) -> Ve
         .flat_map(|x| x.ok())
         .collect();
 
-    flattened_results.sort_by(|a, b| b.price.partial_cmp(&a.price).unwrap());
-    return flattened_results
+    flattened_results.sort_by_key(|result| std::cmp::Reverse(result.price));
+    flattened_results
 }